@@ -0,0 +1,62 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt, HeaderValue};
+
+const CHROME: &str = "gzip, deflate, br, zstd";
+const SAFARI: &str = "gzip, deflate, br";
+const CURL: &str = "deflate, gzip, br, zstd";
+const WEIGHTED: &str = "br;q=1.0, gzip;q=0.8, deflate;q=0.6, identity;q=0.3, *;q=0";
+
+fn worst_case() -> String {
+    let mut s = (0..200)
+        .map(|i| format!("x-ext-{};q=0.{:03}", i, i % 1000))
+        .collect::<Vec<_>>()
+        .join(", ");
+    s.push_str(", gzip;q=0.001, *;q=0");
+    s
+}
+
+fn headers(value: &str) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    map.insert(
+        "accept-encoding",
+        HeaderValue::from_str(value).expect("valid header value"),
+    );
+    map
+}
+
+fn inputs() -> Vec<(&'static str, String)> {
+    vec![
+        ("chrome", CHROME.to_owned()),
+        ("safari", SAFARI.to_owned()),
+        ("curl", CURL.to_owned()),
+        ("weighted", WEIGHTED.to_owned()),
+        ("worst_case", worst_case()),
+    ]
+}
+
+fn decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    for (name, value) in inputs() {
+        let map = headers(&value);
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(&map).typed_get::<AcceptEncoding>().unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn preferred_encoding(c: &mut Criterion) {
+    let available = [Encoding::Zstd, Encoding::Brotli, Encoding::Gzip];
+
+    let mut group = c.benchmark_group("preferred_encoding");
+    for (name, value) in inputs() {
+        let accept_enc = headers(&value).typed_get::<AcceptEncoding>().unwrap();
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(&accept_enc).preferred_encoding(black_box(&available)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, decode, preferred_encoding);
+criterion_main!(benches);
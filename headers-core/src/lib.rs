@@ -1,7 +1,7 @@
 #![deny(missing_docs)]
 #![deny(missing_debug_implementations)]
 #![cfg_attr(test, deny(warnings))]
-#![doc(html_root_url = "https://docs.rs/headers-core/0.3.0")]
+#![doc(html_root_url = "https://docs.rs/headers-core/0.3.1")]
 
 //! # headers-core
 //!
@@ -37,9 +37,15 @@ pub trait Header {
 }
 
 /// Errors trying to decode a header.
+///
+/// When returned from a lookup that knows which header failed, such as
+/// `typed_try_get` in the `headers` crate, the `Display` output includes the
+/// header name and the offending values. Values of headers that carry
+/// credentials, such as `Authorization`, are left out.
 #[derive(Debug)]
 pub struct Error {
     kind: Kind,
+    header: Option<Box<(HeaderName, Vec<HeaderValue>)>>,
 }
 
 #[derive(Debug)]
@@ -52,16 +58,99 @@ impl Error {
     pub fn invalid() -> Error {
         Error {
             kind: Kind::Invalid,
+            header: None,
         }
     }
+
+    /// Attach the name and values of the header that failed to decode.
+    pub fn with_header<'i, I>(mut self, name: &HeaderName, values: I) -> Error
+    where
+        I: IntoIterator<Item = &'i HeaderValue>,
+    {
+        self.header = Some(Box::new((
+            name.clone(),
+            values.into_iter().cloned().collect(),
+        )));
+        self
+    }
+
+    /// The name of the header that failed to decode, if known.
+    pub fn header_name(&self) -> Option<&HeaderName> {
+        self.header.as_ref().map(|header| &header.0)
+    }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match &self.kind {
-            Kind::Invalid => f.write_str("invalid HTTP header"),
+            Kind::Invalid => f.write_str("invalid HTTP header")?,
         }
+        if let Some(header) = &self.header {
+            write!(f, " {}", header.0)?;
+            if is_credential(&header.0) {
+                return Ok(());
+            }
+            for (i, value) in header.1.iter().enumerate() {
+                let sep = if i == 0 { ": " } else { ", " };
+                write!(f, "{}{:?}", sep, value)?;
+            }
+        }
+        Ok(())
     }
 }
 
 impl error::Error for Error {}
+
+/// Whether the values of the header `name` are secrets that must not end up
+/// in logs.
+fn is_credential(name: &HeaderName) -> bool {
+    name == header::AUTHORIZATION
+        || name == header::PROXY_AUTHORIZATION
+        || name == header::COOKIE
+        || name == header::SET_COOKIE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Error::invalid().to_string(), "invalid HTTP header");
+    }
+
+    #[test]
+    fn display_with_header() {
+        let values = [
+            HeaderValue::from_static("forty-two"),
+            HeaderValue::from_static("42"),
+        ];
+        let err = Error::invalid().with_header(&header::CONTENT_LENGTH, &values);
+
+        assert_eq!(err.header_name(), Some(&header::CONTENT_LENGTH));
+        assert_eq!(
+            err.to_string(),
+            "invalid HTTP header content-length: \"forty-two\", \"42\""
+        );
+    }
+
+    #[test]
+    fn display_redacts_set_cookie() {
+        let values = [HeaderValue::from_static("sid=31d4d96e407aad42; Secure")];
+        let err = Error::invalid().with_header(&header::SET_COOKIE, &values);
+        assert_eq!(err.to_string(), "invalid HTTP header set-cookie");
+    }
+
+    #[test]
+    fn display_redacts_credentials() {
+        let values = [HeaderValue::from_static("Basic c2VjcmV0")];
+        for name in &[
+            header::AUTHORIZATION,
+            header::PROXY_AUTHORIZATION,
+            header::COOKIE,
+        ] {
+            let err = Error::invalid().with_header(name, &values);
+            assert_eq!(err.to_string(), format!("invalid HTTP header {}", name));
+        }
+    }
+}
@@ -0,0 +1,1721 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+
+use http::{HeaderName, HeaderValue};
+
+use crate::util::negotiation::Wildcard;
+use crate::util::{reject_trailers, FlatCsv, QualityList};
+use crate::{ContentEncoding, Encoding, Error, Header, Quality, QualityValue};
+
+/// `Accept-Encoding` header, defined in
+/// [RFC7231](https://datatracker.ietf.org/doc/html/rfc7231#section-5.3.4)
+///
+/// The `Accept-Encoding` header field can be used by user agents to
+/// indicate what response content-codings are
+/// acceptable in the response.  An  `identity` token is used as a synonym
+/// for "no encoding" in order to communicate when no encoding is
+/// preferred.
+///
+/// Unlike in [`ContentEncoding`](crate::ContentEncoding), `identity`
+/// entries are meaningful here, and are kept as sent. A `trailers` entry
+/// only belongs in [`Te`](crate::Te), and fails to decode.
+///
+/// Values with obsolete line folding must have been unfolded into spaces
+/// already, since a `HeaderValue` can't contain a line break. The extra
+/// whitespace is then ignored.
+///
+/// Equality and hashing compare the raw header value, so an
+/// `AcceptEncoding` can key a cache of negotiation results. Use
+/// [`semantically_eq`](AcceptEncoding::semantically_eq) to ignore order and
+/// whitespace.
+///
+/// # ABNF
+///
+/// ```text
+/// Accept-Encoding  = #( codings [ weight ] )
+/// codings          = content-coding / "identity" / "*"
+/// ```
+///
+/// # Example values
+/// * `compress, gzip`
+/// * ``
+/// * `*`
+/// * `compress;q=0.5, gzip;q=1`
+/// * `gzip;q=1.0, identity; q=0.5, *;q=0`
+///
+/// # Examples
+///
+/// ```
+/// use headers::{AcceptEncoding, Encoding, QualityValue};
+///
+/// let accept_enc = vec![
+///     QualityValue::from(Encoding::Gzip),
+///     QualityValue::from(Encoding::Brotli),
+/// ]
+/// .into_iter()
+/// .collect::<AcceptEncoding>();
+///
+/// assert_eq!(
+///     accept_enc.preferred_encoding(&[Encoding::Brotli, Encoding::Deflate]),
+///     Some(Encoding::Brotli),
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AcceptEncoding(QualityList<Encoding>);
+
+/// The result of [`AcceptEncoding::negotiate_detailed`], telling why an
+/// encoding was picked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NegotiationOutcome {
+    /// The client prefers this encoding. This is `identity` if the client
+    /// prefers it over those available, or if it was available itself.
+    Selected(Encoding),
+    /// The client accepts none of those available, but still accepts
+    /// `identity`, which is always available.
+    IdentityFallback,
+    /// The client accepts none of those available, and rejected `identity`
+    /// too. The server may respond with `406 Not Acceptable`.
+    NotAcceptable,
+}
+
+impl Header for AcceptEncoding {
+    fn name() -> &'static HeaderName {
+        &::http::header::ACCEPT_ENCODING
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, Error> {
+        let flat = values.collect::<FlatCsv>();
+        reject_trailers(flat.iter())?;
+        Ok(AcceptEncoding(flat.into()))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
+}
+
+impl AcceptEncoding {
+    /// Create an `Accept-Encoding: *` header, accepting any encoding.
+    ///
+    /// This is how a request without `Accept-Encoding` should be treated,
+    /// so a server can fall back to it when the header is missing. It is
+    /// the opposite of an empty header, which only accepts `identity`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt};
+    ///
+    /// let headers = HeaderMap::new();
+    /// let accept_enc = headers
+    ///     .typed_get::<AcceptEncoding>()
+    ///     .unwrap_or_else(AcceptEncoding::all);
+    ///
+    /// assert!(accept_enc.accepts(&Encoding::Gzip));
+    /// assert!(accept_enc.accepts(&Encoding::Identity));
+    /// ```
+    pub fn all() -> AcceptEncoding {
+        AcceptEncoding(HeaderValue::from_static("*").into())
+    }
+
+    /// Create an `Accept-Encoding: gzip, br` header, as most browsers send.
+    pub fn gzip_br() -> AcceptEncoding {
+        AcceptEncoding(HeaderValue::from_static("gzip, br").into())
+    }
+
+    /// Create an `AcceptEncoding` from a slice of quality items.
+    ///
+    /// Like collecting, encodings that are not valid tokens are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding, QualityValue};
+    ///
+    /// let accept_enc = AcceptEncoding::new(&[
+    ///     QualityValue::from(Encoding::Zstd),
+    ///     QualityValue::from(Encoding::Gzip),
+    /// ]);
+    /// assert!(accept_enc.accepts(&Encoding::Zstd));
+    /// ```
+    pub fn new(items: &[QualityValue<Encoding>]) -> AcceptEncoding {
+        items.iter().cloned().collect()
+    }
+
+    /// Create an `AcceptEncoding` from quality items, checking that every
+    /// encoding is a valid token.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding, QualityValue};
+    ///
+    /// let valid = AcceptEncoding::try_from_iter(vec![QualityValue::from(Encoding::Gzip)]);
+    /// assert!(valid.is_ok());
+    ///
+    /// let invalid = AcceptEncoding::try_from_iter(vec![
+    ///     QualityValue::from(Encoding::Ext("not a token".into())),
+    /// ]);
+    /// assert!(invalid.is_err());
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, crate::Error>
+    where
+        I: IntoIterator<Item = QualityValue<Encoding>>,
+    {
+        let items = iter
+            .into_iter()
+            .map(|item| {
+                if is_content_coding(item.value()) {
+                    Ok(item)
+                } else {
+                    Err(crate::Error::invalid())
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(AcceptEncoding(items.into_iter().collect()))
+    }
+
+    /// Create an `AcceptEncoding` from a comma-separated list of plain
+    /// encodings, such as a list of server capabilities from a config file.
+    ///
+    /// Every encoding gets a quality of 1. Entries with parameters, such as
+    /// `gzip;q=0.5`, are rejected, as is anything that isn't a valid token.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding};
+    ///
+    /// let accept_enc = AcceptEncoding::from_encodings_str("gzip, br").unwrap();
+    /// assert!(accept_enc.accepts(&Encoding::Brotli));
+    ///
+    /// assert!(AcceptEncoding::from_encodings_str("gzip;q=0.5, br").is_err());
+    /// ```
+    pub fn from_encodings_str(s: &str) -> Result<Self, crate::Error> {
+        let items = s
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| {
+                if item.contains(';') {
+                    return Err(crate::Error::invalid());
+                }
+                item.parse::<Encoding>().map(QualityValue::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        AcceptEncoding::try_from_iter(items)
+    }
+
+    /// Iterate the quality items, sorted by quality with the highest first.
+    ///
+    /// Items with equal quality keep the order they were sent in. Items that
+    /// cannot be parsed are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = QualityValue<Encoding>> + '_ {
+        self.to_vec().into_iter()
+    }
+
+    /// Get the quality items as an owned `Vec`, sorted like
+    /// [`iter`](AcceptEncoding::iter).
+    pub fn to_vec(&self) -> Vec<QualityValue<Encoding>> {
+        self.0.to_vec()
+    }
+
+    /// Iterate the encodings, sorted by quality with the highest first.
+    ///
+    /// This includes `*` and any encodings the client rejected with `q=0`.
+    pub fn iter_encodings(&self) -> impl Iterator<Item = Encoding> + '_ {
+        self.iter().map(QualityValue::into_value)
+    }
+
+    /// Iterate the encodings the client accepts, sorted by quality with the
+    /// highest first.
+    ///
+    /// Unlike [`iter_encodings`](AcceptEncoding::iter_encodings), encodings
+    /// rejected with `q=0` are skipped. A `*` with a non-zero quality is
+    /// still yielded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("accept-encoding", "gzip;q=0, br".parse().unwrap());
+    /// let accept_enc = headers.typed_get::<AcceptEncoding>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     accept_enc.iter_acceptable_encodings().collect::<Vec<_>>(),
+    ///     vec![Encoding::Brotli],
+    /// );
+    /// ```
+    pub fn iter_acceptable_encodings(&self) -> impl Iterator<Item = Encoding> + '_ {
+        self.iter()
+            .filter(|item| !item.quality().is_zero())
+            .map(QualityValue::into_value)
+    }
+
+    /// View the header value as it was received, such as for logging.
+    ///
+    /// Several `Accept-Encoding` lines are joined with `, `. Returns `None`
+    /// if the value isn't visible ASCII.
+    pub fn as_raw_str(&self) -> Option<&str> {
+        self.0.as_raw_str()
+    }
+
+    /// Iterate the listed encodings with an effective quality above
+    /// `threshold`, sorted by quality with the highest first.
+    ///
+    /// Every encoding is only yielded once. `*` is skipped, while the
+    /// encodings it matches are not listed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt, Quality};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("accept-encoding", "gzip;q=0.3, br;q=0.8".parse().unwrap());
+    /// let accept_enc = headers.typed_get::<AcceptEncoding>().unwrap();
+    ///
+    /// let threshold = Quality::try_from(0.5).unwrap();
+    /// assert_eq!(
+    ///     accept_enc.above_quality(threshold).collect::<Vec<_>>(),
+    ///     vec![Encoding::Brotli],
+    /// );
+    /// ```
+    pub fn above_quality(&self, threshold: Quality) -> impl Iterator<Item = Encoding> + '_ {
+        let mut seen = Vec::new();
+        self.iter_encodings().filter(move |encoding| {
+            if encoding.is_wildcard()
+                || seen.contains(encoding)
+                || self.effective_quality(encoding) <= threshold
+            {
+                return false;
+            }
+            seen.push(encoding.clone());
+            true
+        })
+    }
+
+    /// Check if the header value is empty, as in `Accept-Encoding: `.
+    ///
+    /// An empty value means that only `identity` is acceptable, while a
+    /// missing `Accept-Encoding` header means that any encoding is, like
+    /// [`AcceptEncoding::all`].
+    /// [`accepts`](AcceptEncoding::accepts),
+    /// [`preferred_encoding`](AcceptEncoding::preferred_encoding) and
+    /// [`effective_encodings`](AcceptEncoding::effective_encodings) treat an
+    /// empty header as `identity`.
+    pub fn is_empty_header(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the quality the client effectively assigns to `encoding`.
+    ///
+    /// This is the quality it was listed with, or else the quality of `*`.
+    /// An encoding that is rejected, or not covered by either, has a quality
+    /// of 0. An `identity` that is only implicitly
+    /// [accepted](AcceptEncoding::accepts) has the lowest non-zero quality,
+    /// `0.001`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("accept-encoding", "br;q=0.9, *;q=0.5".parse().unwrap());
+    /// let accept_enc = headers.typed_get::<AcceptEncoding>().unwrap();
+    ///
+    /// assert_eq!(accept_enc.effective_quality(&Encoding::Brotli).to_string(), "0.9");
+    /// assert_eq!(accept_enc.effective_quality(&Encoding::Gzip).to_string(), "0.5");
+    /// ```
+    pub fn effective_quality(&self, encoding: &Encoding) -> Quality {
+        match self.quality_of(encoding) {
+            Some(quality) => quality,
+            None if *encoding == Encoding::Identity => Quality::LOWEST,
+            None => Quality::ZERO,
+        }
+    }
+
+    /// Check if the client accepts the given encoding with a non-zero
+    /// quality, either explicitly or through `*`.
+    ///
+    /// `identity` is always accepted, unless the client rejected it with
+    /// `identity;q=0`, or with `*;q=0` without listing `identity`.
+    pub fn accepts(&self, encoding: &Encoding) -> bool {
+        if *encoding == Encoding::Identity {
+            return self.identity_allowed();
+        }
+        self.quality_of(encoding)
+            .map(|quality| !quality.is_zero())
+            .unwrap_or(false)
+    }
+
+    /// Check if the client accepts `br`, like
+    /// [`accepts`](AcceptEncoding::accepts).
+    pub fn accepts_brotli(&self) -> bool {
+        self.accepts(&Encoding::Brotli)
+    }
+
+    /// Check if the client accepts `gzip`, like
+    /// [`accepts`](AcceptEncoding::accepts).
+    pub fn accepts_gzip(&self) -> bool {
+        self.accepts(&Encoding::Gzip)
+    }
+
+    /// Check if the client accepts `zstd`, like
+    /// [`accepts`](AcceptEncoding::accepts).
+    pub fn accepts_zstd(&self) -> bool {
+        self.accepts(&Encoding::Zstd)
+    }
+
+    /// Check if `encoding` is the only coding the client accepts, besides
+    /// `identity`.
+    ///
+    /// This is useful to special-case legacy clients that only accept
+    /// `gzip`. A `*` with a non-zero quality accepts other codings too, so
+    /// this is then `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("accept-encoding", "gzip, identity".parse().unwrap());
+    /// let accept_enc = headers.typed_get::<AcceptEncoding>().unwrap();
+    ///
+    /// assert!(accept_enc.is_single(&Encoding::Gzip));
+    /// ```
+    pub fn is_single(&self, encoding: &Encoding) -> bool {
+        let mut found = false;
+        for item in self.iter() {
+            let value = item.value();
+            if *value == Encoding::Identity || !self.accepts(value) {
+                continue;
+            }
+            if value != encoding {
+                return false;
+            }
+            found = true;
+        }
+        found
+    }
+
+    /// Compare how the client ranks two encodings by their effective
+    /// quality.
+    ///
+    /// Returns `Ordering::Greater` if `a` is preferred over `b`,
+    /// `Ordering::Less` if `b` is preferred, and `Ordering::Equal` if they
+    /// have the same quality. An encoding that isn't
+    /// [accepted](AcceptEncoding::accepts) ranks below any accepted one,
+    /// and `None` is returned if neither is accepted. An implicitly accepted
+    /// `identity` ranks below any encoding the client listed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("accept-encoding", "br;q=1, gzip;q=0.5".parse().unwrap());
+    /// let accept_enc = headers.typed_get::<AcceptEncoding>().unwrap();
+    ///
+    /// let use_brotli = accept_enc.prefers(&Encoding::Brotli, &Encoding::Gzip)
+    ///     == Some(Ordering::Greater);
+    /// assert!(use_brotli);
+    /// ```
+    pub fn prefers(&self, a: &Encoding, b: &Encoding) -> Option<Ordering> {
+        match (self.rank(a), self.rank(b)) {
+            (None, None) => None,
+            (a, b) => Some(a.cmp(&b)),
+        }
+    }
+
+    /// Select the encoding out of `available` that the client prefers.
+    ///
+    /// The encoding with the highest quality wins, and ties are broken by
+    /// the order of `available`.
+    ///
+    /// `identity` is implicitly available, as
+    /// [RFC7231](https://tools.ietf.org/html/rfc7231#section-5.3.4) says
+    /// no coding is acceptable by default. It competes with `available` at
+    /// its [effective quality](AcceptEncoding::effective_quality), and
+    /// loses ties, so `gzip;q=0.5, identity` selects `identity`. If none of
+    /// `available` is accepted, `identity` is returned unless the client
+    /// rejected it. So `None` means the client accepts neither `available`
+    /// nor `identity`.
+    /// Use [`best_encoding`](crate::best_encoding) to only select from
+    /// `available`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("accept-encoding", "gzip".parse().unwrap());
+    /// let accept_enc = headers.typed_get::<AcceptEncoding>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     accept_enc.preferred_encoding(&[Encoding::Brotli]),
+    ///     Some(Encoding::Identity),
+    /// );
+    /// ```
+    pub fn preferred_encoding(&self, available: &[Encoding]) -> Option<Encoding> {
+        match self.negotiate_detailed(available) {
+            NegotiationOutcome::Selected(encoding) => Some(encoding),
+            NegotiationOutcome::IdentityFallback => Some(Encoding::Identity),
+            NegotiationOutcome::NotAcceptable => None,
+        }
+    }
+
+    /// Select the encoding out of `candidates` like
+    /// [`preferred_encoding`](AcceptEncoding::preferred_encoding), where
+    /// each candidate has a server-side cost, such as of compressing with it.
+    ///
+    /// The client quality still decides first, and ties are broken by the
+    /// lowest cost, and then by the order of `candidates`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("accept-encoding", "br, gzip".parse().unwrap());
+    /// let accept_enc = headers.typed_get::<AcceptEncoding>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     accept_enc.preferred_encoding_weighted(&[(Encoding::Brotli, 10), (Encoding::Gzip, 1)]),
+    ///     Some(Encoding::Gzip),
+    /// );
+    /// ```
+    pub fn preferred_encoding_weighted(&self, candidates: &[(Encoding, u32)]) -> Option<Encoding> {
+        let mut candidates = candidates.to_vec();
+        // Stable, so equal costs keep their order.
+        candidates.sort_by_key(|&(_, cost)| cost);
+        let available = candidates
+            .into_iter()
+            .map(|(encoding, _)| encoding)
+            .collect::<Vec<_>>();
+        self.preferred_encoding(&available)
+    }
+
+    /// Select the encoding out of `available` like
+    /// [`preferred_encoding`](AcceptEncoding::preferred_encoding), telling
+    /// whether `identity` was only picked as a fallback.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt, NegotiationOutcome};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("accept-encoding", "gzip".parse().unwrap());
+    /// let accept_enc = headers.typed_get::<AcceptEncoding>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     accept_enc.negotiate_detailed(&[Encoding::Gzip]),
+    ///     NegotiationOutcome::Selected(Encoding::Gzip),
+    /// );
+    /// assert_eq!(
+    ///     accept_enc.negotiate_detailed(&[Encoding::Brotli]),
+    ///     NegotiationOutcome::IdentityFallback,
+    /// );
+    /// ```
+    pub fn negotiate_detailed(&self, available: &[Encoding]) -> NegotiationOutcome {
+        let best = self.negotiable().negotiate(available, available);
+        self.against_identity(best)
+    }
+
+    /// Select the encoding out of `available` like
+    /// [`preferred_encoding`](AcceptEncoding::preferred_encoding), as the
+    /// `Content-Encoding` of the response.
+    ///
+    /// Returns `None` if the response should be sent without a coding, and
+    /// so without `Content-Encoding`. This is also the case if the client
+    /// rejected `identity` too. Use `preferred_encoding` to respond with
+    /// `406 Not Acceptable` then.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, ContentEncoding, Encoding, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("accept-encoding", "gzip".parse().unwrap());
+    /// let accept_enc = headers.typed_get::<AcceptEncoding>().unwrap();
+    ///
+    /// let content_enc = accept_enc.negotiate_content_encoding(&[Encoding::Gzip]).unwrap();
+    /// assert!(content_enc.contains("gzip"));
+    /// assert!(accept_enc.negotiate_content_encoding(&[Encoding::Brotli]).is_none());
+    /// ```
+    pub fn negotiate_content_encoding(&self, available: &[Encoding]) -> Option<ContentEncoding> {
+        match self.preferred_encoding(available)? {
+            Encoding::Identity => None,
+            encoding => Some(encoding.into()),
+        }
+    }
+
+    /// Select the encoding the client prefers, out of those `is_available`
+    /// returns `true` for.
+    ///
+    /// This is like [`preferred_encoding`](AcceptEncoding::preferred_encoding),
+    /// but for servers that can check whether they support an encoding, but
+    /// not list them. The candidates are the encodings the client listed,
+    /// and, if it sent `*`, the [well-known](Encoding::well_known) content
+    /// codings.
+    /// Ties are broken by the order the client listed them in. `identity`
+    /// is only returned as a fallback if `is_available` allows it as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("accept-encoding", "zstd, *;q=0.5".parse().unwrap());
+    /// let accept_enc = headers.typed_get::<AcceptEncoding>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     accept_enc.preferred_encoding_by(|enc| *enc == Encoding::Gzip),
+    ///     Some(Encoding::Gzip),
+    /// );
+    /// ```
+    pub fn preferred_encoding_by<F>(&self, is_available: F) -> Option<Encoding>
+    where
+        F: Fn(&Encoding) -> bool,
+    {
+        let negotiable = self.negotiable();
+        let items = negotiable.to_vec();
+
+        let listed = items.iter().map(QualityValue::value);
+        // `chunked` and `trailers` are transfer codings, not content codings.
+        let wildcard = if items.iter().any(|item| item.value().is_wildcard()) {
+            Encoding::well_known()
+        } else {
+            &[]
+        }
+        .iter()
+        .filter(|encoding| !matches!(encoding, Encoding::Chunked | Encoding::Trailers));
+
+        let mut available = Vec::new();
+        for encoding in listed.chain(wildcard) {
+            if !encoding.is_wildcard() && !available.contains(encoding) && is_available(encoding) {
+                available.push(encoding.clone());
+            }
+        }
+
+        let best = negotiable.negotiate(&available, &available);
+        if !is_available(&Encoding::Identity) {
+            return best;
+        }
+        match self.against_identity(best) {
+            NegotiationOutcome::Selected(encoding) => Some(encoding),
+            NegotiationOutcome::IdentityFallback => Some(Encoding::Identity),
+            NegotiationOutcome::NotAcceptable => None,
+        }
+    }
+
+    /// Get the encodings the client accepts, sorted by preference.
+    ///
+    /// Encodings rejected with `q=0` are excluded. A `*` is expanded to every
+    /// encoding of `universe` that the client did not otherwise mention.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("accept-encoding", "br;q=0, *".parse().unwrap());
+    /// let accept_enc = headers.typed_get::<AcceptEncoding>().unwrap();
+    ///
+    /// assert_eq!(
+    ///     accept_enc.effective_encodings(&[Encoding::Brotli, Encoding::Gzip]),
+    ///     vec![Encoding::Gzip],
+    /// );
+    /// ```
+    pub fn effective_encodings(&self, universe: &[Encoding]) -> Vec<Encoding> {
+        let items = self.negotiable().to_vec();
+        let mentioned = |encoding: &Encoding| items.iter().any(|item| item.value() == encoding);
+
+        let mut encodings = Vec::new();
+        for item in &items {
+            if item.quality().is_zero() {
+                continue;
+            }
+
+            if item.value().is_wildcard() {
+                for encoding in universe {
+                    if !mentioned(encoding) && !encodings.contains(encoding) {
+                        encodings.push(encoding.clone());
+                    }
+                }
+            } else if !encodings.contains(item.value()) {
+                encodings.push(item.value().clone());
+            }
+        }
+        encodings
+    }
+
+    /// Reduce this header to only `encoding`, such as to forward the single
+    /// encoding a gateway will use to an upstream server.
+    ///
+    /// `identity` is kept as a fallback with `q=0.1`, unless the client
+    /// rejected it, in which case it is rejected with `identity;q=0` too.
+    /// Collapsing to `identity` only advertises `identity`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, Encoding, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("accept-encoding", "gzip, br, zstd;q=0.5".parse().unwrap());
+    /// let accept_enc = headers.typed_get::<AcceptEncoding>().unwrap();
+    ///
+    /// let mut upstream = HeaderMap::new();
+    /// upstream.typed_insert(accept_enc.collapse_to(Encoding::Brotli));
+    /// assert_eq!(upstream["accept-encoding"], "br, identity; q=0.1");
+    /// ```
+    pub fn collapse_to(&self, encoding: Encoding) -> AcceptEncoding {
+        if encoding == Encoding::Identity {
+            return vec![QualityValue::from(Encoding::Identity)]
+                .into_iter()
+                .collect();
+        }
+
+        let identity = if self.identity_allowed() {
+            Quality::from_millis(100).expect("0.1 is a valid quality")
+        } else {
+            Quality::ZERO
+        };
+        vec![
+            QualityValue::from(encoding),
+            QualityValue::new(Encoding::Identity, identity),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    /// Set the quality of `encoding`, replacing any existing entry for it.
+    ///
+    /// An existing entry keeps its position, otherwise the encoding is
+    /// appended. Like when collecting, an encoding that isn't a valid token,
+    /// or `trailers`, is ignored.
+    ///
+    /// Rewriting the value writes every weight as `; q=`, the same as
+    /// encoding quality items.
+    pub fn insert(&mut self, encoding: Encoding, quality: Quality) {
+        if is_content_coding(&encoding) {
+            self.0.insert(encoding, quality);
+        }
+    }
+
+    /// Append `value` to this header verbatim, without parsing it.
+    ///
+    /// Encoding an `AcceptEncoding` always writes back the exact bytes that
+    /// were received, including entries that [`iter`](AcceptEncoding::iter)
+    /// skips because they can't be parsed. This allows merging in more raw
+    /// values, such as when a proxy combines several headers, while keeping
+    /// that guarantee.
+    pub fn append_raw(&mut self, value: &HeaderValue) {
+        self.0.append_raw(value);
+    }
+
+    /// Remove every entry for `encoding`.
+    ///
+    /// Returns whether any entry was removed.
+    pub fn remove(&mut self, encoding: &Encoding) -> bool {
+        self.0.remove(encoding)
+    }
+
+    /// Collapse repeated entries for the same encoding into one.
+    ///
+    /// The entry with the highest quality wins, or the first one if several
+    /// share it, and takes the position of the first entry for that encoding.
+    /// Entries that can't be parsed are kept as they are.
+    pub fn dedup(&mut self) {
+        self.0.dedup();
+    }
+
+    /// Check if both headers accept the same encodings with the same
+    /// qualities, ignoring order and whitespace.
+    ///
+    /// The derived `PartialEq` and `Hash` use the raw header value instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut a = HeaderMap::new();
+    /// a.insert("accept-encoding", "gzip, br;q=0.5".parse().unwrap());
+    /// let mut b = HeaderMap::new();
+    /// b.insert("accept-encoding", "br; q=0.5,gzip".parse().unwrap());
+    ///
+    /// let a = a.typed_get::<AcceptEncoding>().unwrap();
+    /// let b = b.typed_get::<AcceptEncoding>().unwrap();
+    /// assert_ne!(a, b);
+    /// assert!(a.semantically_eq(&b));
+    /// ```
+    pub fn semantically_eq(&self, other: &AcceptEncoding) -> bool {
+        self.0.quality_map() == other.0.quality_map()
+    }
+
+    /// The quality the client assigned to `encoding`, falling back to `*`.
+    fn quality_of(&self, encoding: &Encoding) -> Option<Quality> {
+        self.negotiable().quality_of(encoding)
+    }
+
+    /// `None` if `encoding` isn't accepted, otherwise its quality, which is
+    /// `None` for an implicitly accepted `identity`.
+    fn rank(&self, encoding: &Encoding) -> Option<Option<Quality>> {
+        if self.accepts(encoding) {
+            Some(self.quality_of(encoding))
+        } else {
+            None
+        }
+    }
+
+    /// Let the implicitly available `identity` compete with the `best`
+    /// available encoding at its effective quality, losing ties.
+    fn against_identity(&self, best: Option<Encoding>) -> NegotiationOutcome {
+        let identity = self.effective_quality(&Encoding::Identity);
+        match best {
+            Some(encoding) if self.effective_quality(&encoding) >= identity => {
+                NegotiationOutcome::Selected(encoding)
+            }
+            Some(_) => NegotiationOutcome::Selected(Encoding::Identity),
+            None if !identity.is_zero() => NegotiationOutcome::IdentityFallback,
+            None => NegotiationOutcome::NotAcceptable,
+        }
+    }
+
+    /// Whether the client didn't rule out `identity`.
+    fn identity_allowed(&self) -> bool {
+        self.quality_of(&Encoding::Identity)
+            .map(|quality| !quality.is_zero())
+            .unwrap_or(true)
+    }
+
+    /// The list to negotiate with, where an empty header means `identity`.
+    fn negotiable(&self) -> Cow<'_, QualityList<Encoding>> {
+        if self.is_empty_header() {
+            Cow::Owned(HeaderValue::from_static("identity").into())
+        } else {
+            Cow::Borrowed(&self.0)
+        }
+    }
+}
+
+/// Encodings that are not valid tokens, like an `Encoding::Ext` containing
+/// a space, are skipped. Use [`AcceptEncoding::try_from_iter`] to get an
+/// error instead.
+impl FromIterator<QualityValue<Encoding>> for AcceptEncoding {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = QualityValue<Encoding>>,
+    {
+        let list = iter
+            .into_iter()
+            .filter(|item| is_content_coding(item.value()))
+            .collect();
+        AcceptEncoding(list)
+    }
+}
+
+fn is_content_coding(encoding: &Encoding) -> bool {
+    encoding.is_token() && *encoding != Encoding::Trailers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+    use crate::util::q;
+
+    fn roundtrip(s: &str) {
+        let accept_enc = test_decode::<AcceptEncoding>(&[s]).unwrap();
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], s);
+    }
+
+    #[test]
+    fn rfc_examples() {
+        roundtrip("compress, gzip");
+        roundtrip("");
+        roundtrip("*");
+        roundtrip("compress;q=0.5, gzip");
+        roundtrip("gzip, identity; q=0.5, *;q=0");
+    }
+
+    #[test]
+    fn iter_sorted_by_quality() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["compress;q=0.5, gzip, br"]).unwrap();
+
+        let encodings = accept_enc.iter_encodings().collect::<Vec<_>>();
+        assert_eq!(
+            encodings,
+            vec![Encoding::Gzip, Encoding::Brotli, Encoding::Compress]
+        );
+    }
+
+    #[test]
+    fn unqualified_sorts_before_qualified() {
+        // An item without a weight has the default `q=1`.
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0.5, br"]).unwrap();
+        assert_eq!(
+            accept_enc.to_vec(),
+            vec![
+                QualityValue::new(Encoding::Brotli, q(1000)),
+                QualityValue::new(Encoding::Gzip, q(500)),
+            ]
+        );
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Gzip, Encoding::Brotli]),
+            Some(Encoding::Brotli)
+        );
+    }
+
+    #[test]
+    fn folded() {
+        // A folded value can't be a `HeaderValue` as-is.
+        assert!(HeaderValue::from_str("gzip,\r\n\tbr;q=0.5").is_err());
+
+        // Unfolded by replacing the CRLF with spaces, as RFC7230 allows.
+        let accept_enc =
+            test_decode::<AcceptEncoding>(&["gzip,  \tbr;q=0.5,\t \t deflate"]).unwrap();
+        assert_eq!(
+            accept_enc.iter_encodings().collect::<Vec<_>>(),
+            vec![Encoding::Gzip, Encoding::Deflate, Encoding::Brotli]
+        );
+    }
+
+    #[test]
+    fn oversized_token_is_rejected() {
+        let value = format!("gzip, {}, br;q=0.5", "x".repeat(100_000));
+        let accept_enc = test_decode::<AcceptEncoding>(&[&value]).unwrap();
+
+        assert_eq!(
+            accept_enc.iter_encodings().collect::<Vec<_>>(),
+            vec![Encoding::Gzip, Encoding::Brotli]
+        );
+    }
+
+    #[test]
+    fn to_vec() {
+        let accept_enc =
+            test_decode::<AcceptEncoding>(&["deflate;q=0.2, gzip, br;q=0.8, x;q=bad"]).unwrap();
+
+        let items = accept_enc.to_vec();
+        assert_eq!(items, accept_enc.iter().collect::<Vec<_>>());
+        assert_eq!(
+            items,
+            vec![
+                QualityValue::from(Encoding::Gzip),
+                QualityValue::new(Encoding::Brotli, q(800)),
+                QualityValue::new(Encoding::Deflate, q(200)),
+            ]
+        );
+    }
+
+    #[test]
+    fn iter_acceptable_encodings() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0, br"]).unwrap();
+        assert_eq!(
+            accept_enc.iter_acceptable_encodings().collect::<Vec<_>>(),
+            vec![Encoding::Brotli]
+        );
+        assert_eq!(accept_enc.iter_encodings().count(), 2);
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["*;q=0.5, identity;q=0"]).unwrap();
+        assert_eq!(
+            accept_enc.iter_acceptable_encodings().collect::<Vec<_>>(),
+            vec![Encoding::star()]
+        );
+    }
+
+    #[test]
+    fn as_raw_str() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0.5,br"]).unwrap();
+        assert_eq!(accept_enc.as_raw_str(), Some("gzip;q=0.5,br"));
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip", "br;q=0.1"]).unwrap();
+        assert_eq!(accept_enc.as_raw_str(), Some("gzip, br;q=0.1"));
+
+        let value = HeaderValue::from_bytes(b"gzip, x-\xff").unwrap();
+        let accept_enc = AcceptEncoding(QualityList::from(value));
+        assert_eq!(accept_enc.as_raw_str(), None);
+    }
+
+    #[test]
+    fn above_quality() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0.3, br;q=0.8"]).unwrap();
+        assert_eq!(
+            accept_enc.above_quality(q(500)).collect::<Vec<_>>(),
+            vec![Encoding::Brotli]
+        );
+        assert_eq!(
+            accept_enc.above_quality(q(0)).collect::<Vec<_>>(),
+            vec![Encoding::Brotli, Encoding::Gzip]
+        );
+        assert_eq!(accept_enc.above_quality(q(800)).count(), 0);
+
+        // Duplicates use the highest quality, and `*` is skipped.
+        let accept_enc =
+            test_decode::<AcceptEncoding>(&["zstd;q=0.4, *;q=0.9, zstd;q=0.6"]).unwrap();
+        assert_eq!(
+            accept_enc.above_quality(q(500)).collect::<Vec<_>>(),
+            vec![Encoding::Zstd]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let accept_enc = test_decode::<AcceptEncoding>(&[""]).unwrap();
+        assert_eq!(accept_enc.iter().count(), 0);
+    }
+
+    #[test]
+    fn all() {
+        let all = AcceptEncoding::all();
+        assert!(all.accepts(&Encoding::Gzip));
+        assert!(all.accepts(&Encoding::Identity));
+        assert!(all.accepts(&"x-custom".parse().unwrap()));
+        assert!(!all.is_empty_header());
+
+        let empty = test_decode::<AcceptEncoding>(&[""]).unwrap();
+        assert_ne!(all, empty);
+        assert!(!all.semantically_eq(&empty));
+        assert!(!empty.accepts(&Encoding::Gzip));
+
+        let headers = test_encode(all);
+        assert_eq!(headers["accept-encoding"], "*");
+    }
+
+    #[test]
+    fn new_from_slice() {
+        let accept_enc = AcceptEncoding::new(&[
+            QualityValue::new(Encoding::Gzip, q(500)),
+            QualityValue::from(Encoding::Brotli),
+            QualityValue::from(Encoding::Ext("not a token".into())),
+        ]);
+        assert_eq!(
+            accept_enc.iter_encodings().collect::<Vec<_>>(),
+            [Encoding::Brotli, Encoding::Gzip]
+        );
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip; q=0.5, br");
+    }
+
+    #[test]
+    fn gzip_br() {
+        let accept_enc = AcceptEncoding::gzip_br();
+        assert!(accept_enc.accepts_gzip());
+        assert!(accept_enc.accepts_brotli());
+        assert!(!accept_enc.accepts_zstd());
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip, br");
+    }
+
+    #[test]
+    fn empty_header_is_identity_only() {
+        let accept_enc = test_decode::<AcceptEncoding>(&[""]).unwrap();
+
+        assert!(accept_enc.is_empty_header());
+        assert!(accept_enc.accepts(&Encoding::Identity));
+        assert!(!accept_enc.accepts(&Encoding::Gzip));
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Gzip, Encoding::Identity]),
+            Some(Encoding::Identity)
+        );
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Gzip]),
+            Some(Encoding::Identity)
+        );
+        assert_eq!(
+            accept_enc.effective_encodings(&[Encoding::Gzip]),
+            vec![Encoding::Identity]
+        );
+    }
+
+    #[test]
+    fn not_empty_header() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip"]).unwrap();
+        assert!(!accept_enc.is_empty_header());
+    }
+
+    #[test]
+    fn from_iter_with_q() {
+        let accept_enc: AcceptEncoding = vec![
+            QualityValue::from(Encoding::Gzip),
+            QualityValue::new(Encoding::Brotli, q(600)),
+        ]
+        .into_iter()
+        .collect();
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip, br; q=0.6");
+    }
+
+    #[test]
+    fn from_iter_omits_explicit_q_1() {
+        let accept_enc: AcceptEncoding = vec![
+            QualityValue::new(Encoding::Gzip, q(1000)),
+            "br;q=1.000".parse().unwrap(),
+        ]
+        .into_iter()
+        .collect();
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip, br");
+    }
+
+    #[test]
+    fn try_from_iter_invalid_ext() {
+        let items = vec![
+            QualityValue::from(Encoding::Gzip),
+            QualityValue::from(Encoding::Ext("x\u{7f}y".into())),
+        ];
+
+        assert!(AcceptEncoding::try_from_iter(items.clone()).is_err());
+
+        let accept_enc = items.into_iter().collect::<AcceptEncoding>();
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip");
+    }
+
+    #[test]
+    fn from_encodings_str() {
+        let accept_enc = AcceptEncoding::from_encodings_str(" gzip,br ,, zstd").unwrap();
+        assert_eq!(
+            accept_enc.to_vec(),
+            vec![
+                QualityValue::from(Encoding::Gzip),
+                QualityValue::from(Encoding::Brotli),
+                QualityValue::from(Encoding::Zstd),
+            ]
+        );
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip, br, zstd");
+    }
+
+    #[test]
+    fn from_encodings_str_rejects_quality() {
+        assert!(AcceptEncoding::from_encodings_str("gzip;q=0.5").is_err());
+        assert!(AcceptEncoding::from_encodings_str("gzip, br; q=1").is_err());
+        assert!(AcceptEncoding::from_encodings_str("gzip;level=9").is_err());
+        assert!(AcceptEncoding::from_encodings_str("not a token").is_err());
+    }
+
+    #[test]
+    fn trailers_is_invalid() {
+        assert_eq!(test_decode::<AcceptEncoding>(&["trailers"]), None);
+        assert_eq!(
+            test_decode::<AcceptEncoding>(&["gzip", "Trailers;q=0.5"]),
+            None
+        );
+
+        let items = vec![
+            QualityValue::from(Encoding::Gzip),
+            QualityValue::from(Encoding::Trailers),
+        ];
+        assert!(AcceptEncoding::try_from_iter(items.clone()).is_err());
+
+        let headers = test_encode(items.into_iter().collect::<AcceptEncoding>());
+        assert_eq!(headers["accept-encoding"], "gzip");
+    }
+
+    #[test]
+    fn try_from_iter_valid() {
+        let accept_enc = AcceptEncoding::try_from_iter(vec![
+            QualityValue::from(Encoding::Ext("x-custom".into())),
+            QualityValue::new(Encoding::star(), q(0)),
+        ])
+        .unwrap();
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "x-custom, *; q=0");
+    }
+
+    #[test]
+    fn effective_quality() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0.8, br;q=0, *;q=0.1"]).unwrap();
+
+        assert_eq!(accept_enc.effective_quality(&Encoding::Gzip), q(800));
+        assert_eq!(accept_enc.effective_quality(&Encoding::Deflate), q(100));
+        assert_eq!(accept_enc.effective_quality(&Encoding::Brotli), q(0));
+        assert_eq!(accept_enc.effective_quality(&Encoding::Identity), q(100));
+    }
+
+    #[test]
+    fn star_matches_any_coding() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["*"]).unwrap();
+
+        assert!(accept_enc.accepts(&Encoding::Gzip));
+        assert!(accept_enc.accepts(&"x-custom".parse().unwrap()));
+
+        // `*/*` is the `Accept` wildcard, and is just an unknown coding here.
+        let accept_enc = test_decode::<AcceptEncoding>(&["*/*"]).unwrap();
+        assert!(!accept_enc.accepts(&Encoding::Gzip));
+    }
+
+    #[test]
+    fn effective_quality_without_wildcard() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip"]).unwrap();
+
+        assert_eq!(accept_enc.effective_quality(&Encoding::Gzip), q(1000));
+        assert_eq!(accept_enc.effective_quality(&Encoding::Zstd), q(0));
+        assert_eq!(accept_enc.effective_quality(&Encoding::Identity), q(1));
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip, identity;q=0"]).unwrap();
+        assert_eq!(accept_enc.effective_quality(&Encoding::Identity), q(0));
+
+        let accept_enc = test_decode::<AcceptEncoding>(&[""]).unwrap();
+        assert_eq!(accept_enc.effective_quality(&Encoding::Identity), q(1000));
+        assert_eq!(accept_enc.effective_quality(&Encoding::Gzip), q(0));
+    }
+
+    #[test]
+    fn accepts() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip, br;q=0, *;q=0.1"]).unwrap();
+
+        assert!(accept_enc.accepts(&Encoding::Gzip));
+        assert!(!accept_enc.accepts(&Encoding::Brotli));
+        assert!(accept_enc.accepts(&Encoding::Deflate));
+    }
+
+    #[test]
+    fn accepts_shortcuts() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip, deflate"]).unwrap();
+        assert!(accept_enc.accepts_gzip());
+        assert!(!accept_enc.accepts_brotli());
+        assert!(!accept_enc.accepts_zstd());
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["br;q=0, *"]).unwrap();
+        assert!(accept_enc.accepts_gzip());
+        assert!(!accept_enc.accepts_brotli());
+        assert!(accept_enc.accepts_zstd());
+    }
+
+    #[test]
+    fn is_single() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip"]).unwrap();
+        assert!(accept_enc.is_single(&Encoding::Gzip));
+        assert!(!accept_enc.is_single(&Encoding::Brotli));
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip, br"]).unwrap();
+        assert!(!accept_enc.is_single(&Encoding::Gzip));
+
+        let accept_enc =
+            test_decode::<AcceptEncoding>(&["gzip;q=0.5, br;q=0, identity, *;q=0"]).unwrap();
+        assert!(accept_enc.is_single(&Encoding::Gzip));
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip, *;q=0.1"]).unwrap();
+        assert!(!accept_enc.is_single(&Encoding::Gzip));
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0"]).unwrap();
+        assert!(!accept_enc.is_single(&Encoding::Gzip));
+    }
+
+    #[test]
+    fn prefers() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["br;q=1, gzip;q=0.5"]).unwrap();
+        assert_eq!(
+            accept_enc.prefers(&Encoding::Brotli, &Encoding::Gzip),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            accept_enc.prefers(&Encoding::Gzip, &Encoding::Brotli),
+            Some(Ordering::Less)
+        );
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["br;q=0.5, gzip;q=1"]).unwrap();
+        assert_eq!(
+            accept_enc.prefers(&Encoding::Brotli, &Encoding::Gzip),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            accept_enc.prefers(&Encoding::Gzip, &Encoding::Brotli),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn prefers_equal_and_unacceptable() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["br, gzip, zstd;q=0"]).unwrap();
+        assert_eq!(
+            accept_enc.prefers(&Encoding::Brotli, &Encoding::Gzip),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            accept_enc.prefers(&Encoding::Zstd, &Encoding::Gzip),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            accept_enc.prefers(&Encoding::Zstd, &Encoding::Deflate),
+            None
+        );
+        assert_eq!(
+            accept_enc.prefers(&Encoding::Identity, &Encoding::Gzip),
+            Some(Ordering::Less)
+        );
+    }
+
+    #[test]
+    fn preferred_encoding() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0.5, br"]).unwrap();
+
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Gzip, Encoding::Brotli]),
+            Some(Encoding::Brotli)
+        );
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Gzip, Encoding::Deflate]),
+            Some(Encoding::Gzip)
+        );
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Deflate]),
+            Some(Encoding::Identity)
+        );
+    }
+
+    #[test]
+    fn preferred_encoding_identity_competes() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0.5, identity"]).unwrap();
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Gzip]),
+            Some(Encoding::Identity)
+        );
+        assert_eq!(
+            accept_enc.negotiate_detailed(&[Encoding::Gzip]),
+            NegotiationOutcome::Selected(Encoding::Identity)
+        );
+        assert!(accept_enc
+            .negotiate_content_encoding(&[Encoding::Gzip])
+            .is_none());
+        assert_eq!(
+            accept_enc
+                .preferred_encoding_by(|enc| *enc == Encoding::Gzip || *enc == Encoding::Identity),
+            Some(Encoding::Identity)
+        );
+        assert_eq!(
+            accept_enc.preferred_encoding_by(|enc| *enc == Encoding::Gzip),
+            Some(Encoding::Gzip)
+        );
+
+        // `identity` loses ties, also through `*`, and its implicit
+        // quality is the lowest.
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip, identity"]).unwrap();
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Gzip]),
+            Some(Encoding::Gzip)
+        );
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0.5, *"]).unwrap();
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Gzip]),
+            Some(Encoding::Identity)
+        );
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0.001"]).unwrap();
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Gzip]),
+            Some(Encoding::Gzip)
+        );
+    }
+
+    #[test]
+    fn negotiate_detailed() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0.5, br"]).unwrap();
+        assert_eq!(
+            accept_enc.negotiate_detailed(&[Encoding::Gzip, Encoding::Brotli]),
+            NegotiationOutcome::Selected(Encoding::Brotli)
+        );
+        assert_eq!(
+            accept_enc.negotiate_detailed(&[Encoding::Zstd]),
+            NegotiationOutcome::IdentityFallback
+        );
+        assert_eq!(
+            accept_enc.negotiate_detailed(&[]),
+            NegotiationOutcome::IdentityFallback
+        );
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0, identity;q=0"]).unwrap();
+        assert_eq!(
+            accept_enc.negotiate_detailed(&[Encoding::Gzip]),
+            NegotiationOutcome::NotAcceptable
+        );
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["br, *;q=0"]).unwrap();
+        assert_eq!(
+            accept_enc.negotiate_detailed(&[Encoding::Gzip]),
+            NegotiationOutcome::NotAcceptable
+        );
+    }
+
+    #[test]
+    fn negotiate_content_encoding() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip, br;q=0.5"]).unwrap();
+
+        let content_enc = accept_enc
+            .negotiate_content_encoding(&[Encoding::Brotli, Encoding::Gzip])
+            .unwrap();
+        let headers = test_encode(content_enc);
+        assert_eq!(headers["content-encoding"], "gzip");
+
+        assert!(accept_enc
+            .negotiate_content_encoding(&[Encoding::Zstd])
+            .is_none());
+        assert!(accept_enc
+            .negotiate_content_encoding(&[Encoding::Identity])
+            .is_none());
+    }
+
+    #[test]
+    fn collapse_to() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0.5, br, zstd"]).unwrap();
+
+        let headers = test_encode(accept_enc.collapse_to(Encoding::Gzip));
+        assert_eq!(headers["accept-encoding"], "gzip, identity; q=0.1");
+
+        let headers = test_encode(accept_enc.collapse_to(Encoding::Identity));
+        assert_eq!(headers["accept-encoding"], "identity");
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["br, *;q=0"]).unwrap();
+        let headers = test_encode(accept_enc.collapse_to(Encoding::Brotli));
+        assert_eq!(headers["accept-encoding"], "br, identity; q=0");
+    }
+
+    #[test]
+    fn preferred_encoding_implicit_identity() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip"]).unwrap();
+        assert!(accept_enc.accepts(&Encoding::Identity));
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Brotli]),
+            Some(Encoding::Identity)
+        );
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Brotli, Encoding::Gzip]),
+            Some(Encoding::Gzip)
+        );
+
+        // An explicit quality still competes with the available encodings.
+        let accept_enc = test_decode::<AcceptEncoding>(&["identity, gzip;q=0.5"]).unwrap();
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Gzip, Encoding::Identity]),
+            Some(Encoding::Identity)
+        );
+    }
+
+    #[test]
+    fn preferred_encoding_identity_rejected() {
+        for s in &["gzip, identity;q=0", "gzip, *;q=0"] {
+            let accept_enc = test_decode::<AcceptEncoding>(&[s]).unwrap();
+            assert!(!accept_enc.accepts(&Encoding::Identity), "{:?}", s);
+            assert_eq!(accept_enc.preferred_encoding(&[Encoding::Brotli]), None);
+        }
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["identity;q=0.1, *;q=0"]).unwrap();
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Brotli]),
+            Some(Encoding::Identity)
+        );
+    }
+
+    #[test]
+    fn preferred_encoding_tie_uses_available_order() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip, br"]).unwrap();
+
+        assert_eq!(
+            accept_enc.preferred_encoding(&[Encoding::Brotli, Encoding::Gzip]),
+            Some(Encoding::Brotli)
+        );
+    }
+
+    #[test]
+    fn preferred_encoding_weighted() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["br, gzip, zstd;q=0.5"]).unwrap();
+        let candidates = [
+            (Encoding::Brotli, 10),
+            (Encoding::Zstd, 0),
+            (Encoding::Gzip, 1),
+        ];
+        // Cheap gzip wins the tie with brotli, but cheaper zstd has a lower
+        // client quality.
+        assert_eq!(
+            accept_enc.preferred_encoding_weighted(&candidates),
+            Some(Encoding::Gzip)
+        );
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["br, gzip;q=0.9"]).unwrap();
+        assert_eq!(
+            accept_enc.preferred_encoding_weighted(&candidates),
+            Some(Encoding::Brotli)
+        );
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["deflate"]).unwrap();
+        assert_eq!(
+            accept_enc.preferred_encoding_weighted(&candidates),
+            Some(Encoding::Identity)
+        );
+    }
+
+    #[test]
+    fn preferred_encoding_by_only_brotli() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip, deflate;q=0.8, br;q=0.5"]).unwrap();
+
+        assert_eq!(
+            accept_enc.preferred_encoding_by(|enc| *enc == Encoding::Brotli),
+            Some(Encoding::Brotli)
+        );
+    }
+
+    #[test]
+    fn preferred_encoding_by_wildcard() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;q=0.5, br;q=0, *;q=0.8"]).unwrap();
+
+        assert_eq!(
+            accept_enc.preferred_encoding_by(|enc| *enc == Encoding::Brotli),
+            None
+        );
+        assert_eq!(
+            accept_enc.preferred_encoding_by(|enc| *enc == Encoding::Zstd),
+            Some(Encoding::Zstd)
+        );
+        assert_eq!(
+            accept_enc.preferred_encoding_by(|enc| *enc != Encoding::Identity),
+            Some(Encoding::Deflate)
+        );
+    }
+
+    #[test]
+    fn preferred_encoding_by_nothing_available() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip"]).unwrap();
+
+        assert_eq!(accept_enc.preferred_encoding_by(|_| false), None);
+        assert_eq!(
+            accept_enc.preferred_encoding_by(|enc| *enc == Encoding::Identity),
+            Some(Encoding::Identity)
+        );
+    }
+
+    #[test]
+    fn effective_encodings_expands_star() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip, *;q=0.1"]).unwrap();
+
+        let universe = [Encoding::Gzip, Encoding::Brotli, Encoding::Deflate];
+        assert_eq!(
+            accept_enc.effective_encodings(&universe),
+            vec![Encoding::Gzip, Encoding::Brotli, Encoding::Deflate]
+        );
+    }
+
+    #[test]
+    fn effective_encodings_excludes_rejected() {
+        let accept_enc = test_decode::<AcceptEncoding>(&["br;q=0, deflate;q=0.5, *"]).unwrap();
+
+        let universe = [Encoding::Gzip, Encoding::Brotli, Encoding::Deflate];
+        assert_eq!(
+            accept_enc.effective_encodings(&universe),
+            vec![Encoding::Gzip, Encoding::Deflate]
+        );
+    }
+
+    #[test]
+    fn encode_is_lossless() {
+        // Odd spacing, parameters, an invalid quality and a stray separator.
+        for s in &[
+            "gzip;level=9 ;  q=0.5,x-Foo;q=0.1234 ,,br",
+            "  identity  ",
+            "gzip; q=1.5, compress",
+        ] {
+            roundtrip(s);
+        }
+
+        let accept_enc = test_decode::<AcceptEncoding>(&["gzip;level=9, x-foo;q=0.1234"]).unwrap();
+        assert_eq!(accept_enc.iter().count(), 1);
+    }
+
+    #[test]
+    fn parameterized_coding_roundtrip() {
+        let raw = "aes128gcm; keyid=\"a,b;c\"; q=0.5, gzip;level=9";
+        let mut accept_enc = test_decode::<AcceptEncoding>(&[raw]).unwrap();
+
+        assert_eq!(
+            accept_enc.to_vec(),
+            vec![
+                QualityValue::from(Encoding::Gzip),
+                QualityValue::new(Encoding::Ext("aes128gcm".into()), q(500)),
+            ]
+        );
+
+        let headers = test_encode(accept_enc.clone());
+        assert_eq!(headers["accept-encoding"], raw);
+
+        accept_enc.insert(Encoding::Brotli, q(1000));
+        accept_enc.dedup();
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], format!("{}, br", raw));
+    }
+
+    #[test]
+    fn append_raw() {
+        let mut accept_enc = test_decode::<AcceptEncoding>(&["gzip"]).unwrap();
+        accept_enc.append_raw(&HeaderValue::from_static("x-odd;q=0.1234"));
+        accept_enc.append_raw(&HeaderValue::from_static("br;q=0.5"));
+
+        assert_eq!(
+            accept_enc.iter_encodings().collect::<Vec<_>>(),
+            vec![Encoding::Gzip, Encoding::Brotli]
+        );
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip, x-odd;q=0.1234, br;q=0.5");
+    }
+
+    #[test]
+    fn append_raw_to_empty() {
+        let mut accept_enc = test_decode::<AcceptEncoding>(&[""]).unwrap();
+        accept_enc.append_raw(&HeaderValue::from_static("gzip"));
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip");
+    }
+
+    #[test]
+    fn insert_new() {
+        let mut accept_enc = test_decode::<AcceptEncoding>(&["gzip, br;q=0.5"]).unwrap();
+        accept_enc.insert(Encoding::Zstd, q(900));
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip, br; q=0.5, zstd; q=0.9");
+    }
+
+    #[test]
+    fn insert_invalid_is_ignored() {
+        let mut accept_enc = test_decode::<AcceptEncoding>(&["gzip"]).unwrap();
+        accept_enc.insert(Encoding::Ext("a\u{7f}".into()), q(500));
+        accept_enc.insert(Encoding::Ext("not a token".into()), q(500));
+        accept_enc.insert(Encoding::Trailers, q(500));
+        assert!(!accept_enc.remove(&Encoding::Ext("a\u{7f}".into())));
+        accept_enc.dedup();
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip");
+    }
+
+    #[test]
+    fn insert_overwrites() {
+        let mut accept_enc =
+            test_decode::<AcceptEncoding>(&["gzip, br;q=0.5, deflate, BR"]).unwrap();
+        accept_enc.insert(Encoding::Brotli, q(1000));
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip, br, deflate");
+    }
+
+    #[test]
+    fn insert_into_empty() {
+        let mut accept_enc = test_decode::<AcceptEncoding>(&[""]).unwrap();
+        accept_enc.insert(Encoding::Gzip, q(500));
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip; q=0.5");
+    }
+
+    #[test]
+    fn remove() {
+        let mut accept_enc = test_decode::<AcceptEncoding>(&["gzip, br;q=0.5, deflate"]).unwrap();
+
+        assert!(accept_enc.remove(&Encoding::Brotli));
+        assert!(!accept_enc.remove(&Encoding::Zstd));
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip, deflate");
+    }
+
+    #[test]
+    fn dedup() {
+        let mut accept_enc = test_decode::<AcceptEncoding>(&["gzip, gzip;q=0.5"]).unwrap();
+        accept_enc.dedup();
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "gzip");
+    }
+
+    #[test]
+    fn dedup_keeps_highest_at_first_position() {
+        let mut accept_enc = test_decode::<AcceptEncoding>(&[
+            "br;q=0.2, gzip;q=0.5, x;q=9, BR;q=0.8, gzip;q=0.5, br;q=0.8",
+        ])
+        .unwrap();
+        accept_enc.dedup();
+
+        let headers = test_encode(accept_enc);
+        assert_eq!(headers["accept-encoding"], "BR; q=0.8, gzip; q=0.5, x;q=9");
+    }
+
+    #[test]
+    fn hash_set() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        set.insert(test_decode::<AcceptEncoding>(&["gzip, br"]).unwrap());
+        set.insert(test_decode::<AcceptEncoding>(&["gzip", "br"]).unwrap());
+        assert_eq!(set.len(), 1);
+
+        set.insert(test_decode::<AcceptEncoding>(&["br, gzip"]).unwrap());
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn semantically_eq_ignores_order_and_whitespace() {
+        let a = test_decode::<AcceptEncoding>(&["gzip, br;q=0.5, *;q=0"]).unwrap();
+        let b = test_decode::<AcceptEncoding>(&["*;q=0,br ; q=0.5,gzip"]).unwrap();
+        let c = test_decode::<AcceptEncoding>(&["*;q=0", "GZIP, br;q=0.500"]).unwrap();
+
+        assert_ne!(a, b);
+        assert!(a.semantically_eq(&b));
+        assert!(a.semantically_eq(&c));
+    }
+
+    #[test]
+    fn semantically_eq_compares_qualities() {
+        let a = test_decode::<AcceptEncoding>(&["gzip, br;q=0.5"]).unwrap();
+        let b = test_decode::<AcceptEncoding>(&["gzip, br;q=0.6"]).unwrap();
+        let c = test_decode::<AcceptEncoding>(&["gzip"]).unwrap();
+
+        assert!(!a.semantically_eq(&b));
+        assert!(!a.semantically_eq(&c));
+    }
+
+    bench_header!(bench_firefox, AcceptEncoding, "gzip, deflate, br, zstd");
+
+    proptest::proptest! {
+        #[test]
+        fn roundtrip_semantically_eq(
+            items in proptest::collection::vec(
+                proptest::prelude::any::<(Encoding, Quality)>(),
+                0..8,
+            ),
+        ) {
+            let accept_enc = items
+                .into_iter()
+                .map(|(encoding, quality)| QualityValue::new(encoding, quality))
+                .collect::<AcceptEncoding>();
+
+            let headers = test_encode(accept_enc.clone());
+            let value = headers["accept-encoding"].to_str().unwrap();
+            let decoded = test_decode::<AcceptEncoding>(&[value]).unwrap();
+            proptest::prop_assert!(decoded.semantically_eq(&accept_enc), "{}", value);
+        }
+    }
+}
@@ -59,9 +59,27 @@ impl Header for AccessControlAllowCredentials {
 
 #[cfg(test)]
 mod tests {
-    use super::super::test_decode;
+    use super::super::{test_decode, test_encode};
     use super::*;
 
+    #[test]
+    fn only_true() {
+        assert_eq!(
+            test_decode::<AccessControlAllowCredentials>(&["true"]),
+            Some(AccessControlAllowCredentials)
+        );
+
+        for s in &["false", "1", "", " true", "true, true"] {
+            assert_eq!(test_decode::<AccessControlAllowCredentials>(&[s]), None);
+        }
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(AccessControlAllowCredentials);
+        assert_eq!(headers["access-control-allow-credentials"], "true");
+    }
+
     #[test]
     fn allow_credentials_is_case_sensitive() {
         let allow_header = test_decode::<AccessControlAllowCredentials>(&["true"]);
@@ -19,6 +19,7 @@ use crate::util::FlatCsv;
 ///
 /// # Example values
 /// * `accept-language, date`
+/// * `*`
 ///
 /// # Examples
 ///
@@ -40,6 +41,42 @@ derive_header! {
 }
 
 impl AccessControlAllowHeaders {
+    /// Create an `Access-Control-Allow-Headers: *` header, allowing any
+    /// header.
+    pub fn any() -> Self {
+        AccessControlAllowHeaders(HeaderValue::from_static("*").into())
+    }
+
+    /// Check if this is `Access-Control-Allow-Headers: *`.
+    ///
+    /// Note that browsers only honor `*` for requests without credentials.
+    pub fn is_any(&self) -> bool {
+        self.0.iter().any(|s| s == "*")
+    }
+
+    /// Check if the given header is allowed, either by name or through `*`.
+    ///
+    /// As the [Fetch standard](https://fetch.spec.whatwg.org/#http-access-control-allow-headers)
+    /// defines, `*` doesn't cover `Authorization`, which has to be listed by
+    /// name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::AccessControlAllowHeaders;
+    /// use http::header::{AUTHORIZATION, CONTENT_TYPE};
+    ///
+    /// let allow_headers = AccessControlAllowHeaders::any();
+    /// assert!(allow_headers.contains(&CONTENT_TYPE));
+    /// assert!(!allow_headers.contains(&AUTHORIZATION));
+    /// ```
+    pub fn contains(&self, name: &HeaderName) -> bool {
+        self.0.iter().any(|s| {
+            s.eq_ignore_ascii_case(name.as_str())
+                || (s == "*" && name != ::http::header::AUTHORIZATION)
+        })
+    }
+
     /// Returns an iterator over `HeaderName`s contained within.
     pub fn iter(&self) -> impl Iterator<Item = HeaderName> + '_ {
         self.0
@@ -89,6 +126,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn contains() {
+        let allow_headers =
+            test_decode::<AccessControlAllowHeaders>(&["Content-Type, x-custom"]).unwrap();
+
+        assert!(!allow_headers.is_any());
+        assert!(allow_headers.contains(&::http::header::CONTENT_TYPE));
+        assert!(allow_headers.contains(&HeaderName::from_static("x-custom")));
+        assert!(!allow_headers.contains(&::http::header::ACCEPT));
+    }
+
+    #[test]
+    fn contains_any() {
+        let allow_headers = test_decode::<AccessControlAllowHeaders>(&["*"]).unwrap();
+
+        assert!(allow_headers.is_any());
+        assert!(allow_headers.contains(&::http::header::CONTENT_TYPE));
+        assert!(allow_headers.contains(&HeaderName::from_static("x-custom")));
+        assert!(!allow_headers.contains(&::http::header::AUTHORIZATION));
+
+        let allow_headers =
+            test_decode::<AccessControlAllowHeaders>(&["*, Authorization"]).unwrap();
+        assert!(allow_headers.contains(&::http::header::AUTHORIZATION));
+
+        let headers = test_encode(AccessControlAllowHeaders::any());
+        assert_eq!(headers["access-control-allow-headers"], "*");
+    }
+
     #[test]
     fn test_with_invalid() {
         let allow_headers = test_decode::<AccessControlAllowHeaders>(&["foo foo, bar"]).unwrap();
@@ -1,8 +1,8 @@
 use std::iter::FromIterator;
 
-use http::{HeaderValue, Method};
+use http::Method;
 
-use crate::util::FlatCsv;
+use crate::util::MethodSet;
 
 /// `Access-Control-Allow-Methods` header, part of
 /// [CORS](http://www.w3.org/TR/cors/#access-control-allow-methods-response-header)
@@ -32,7 +32,7 @@ use crate::util::FlatCsv;
 ///     .collect::<AccessControlAllowMethods>();
 /// ```
 #[derive(Clone, Debug, PartialEq)]
-pub struct AccessControlAllowMethods(FlatCsv);
+pub struct AccessControlAllowMethods(MethodSet);
 
 derive_header! {
     AccessControlAllowMethods(_),
@@ -42,7 +42,7 @@ derive_header! {
 impl AccessControlAllowMethods {
     /// Returns an iterator over `Method`s contained within.
     pub fn iter(&self) -> impl Iterator<Item = Method> + '_ {
-        self.0.iter().filter_map(|s| s.parse().ok())
+        self.0.iter().cloned()
     }
 }
 
@@ -51,17 +51,7 @@ impl FromIterator<Method> for AccessControlAllowMethods {
     where
         I: IntoIterator<Item = Method>,
     {
-        let methods = iter
-            .into_iter()
-            .map(|method| {
-                method
-                    .as_str()
-                    .parse::<HeaderValue>()
-                    .expect("Method is a valid HeaderValue")
-            })
-            .collect();
-
-        AccessControlAllowMethods(methods)
+        AccessControlAllowMethods(iter.into_iter().collect())
     }
 }
 
@@ -80,6 +70,22 @@ mod tests {
         assert_eq!(as_vec[1], Method::PUT);
     }
 
+    #[test]
+    fn dedup_case_insensitive() {
+        let allowed =
+            test_decode::<AccessControlAllowMethods>(&["put, DELETE", "PUT, XMODIFY"]).unwrap();
+
+        let as_vec = allowed.iter().collect::<Vec<_>>();
+        assert_eq!(
+            as_vec,
+            vec![
+                Method::PUT,
+                Method::DELETE,
+                Method::from_bytes(b"XMODIFY").unwrap()
+            ]
+        );
+    }
+
     #[test]
     fn from_iter() {
         let allow: AccessControlAllowMethods = vec![Method::GET, Method::PUT].into_iter().collect();
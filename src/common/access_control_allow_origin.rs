@@ -34,6 +34,12 @@ use crate::Error;
 /// let null_origin = AccessControlAllowOrigin::NULL;
 /// let origin = AccessControlAllowOrigin::try_from("http://web-platform.test:8000");
 /// ```
+///
+/// # Credentials
+///
+/// Browsers refuse `*` in the response to a request with credentials, so
+/// such a response has to echo the specific origin instead. This isn't
+/// enforced here, since it depends on the request.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct AccessControlAllowOrigin(OriginOrAny);
 
@@ -63,6 +69,40 @@ impl AccessControlAllowOrigin {
             _ => None,
         }
     }
+
+    /// Check if the given `Origin` value, as sent by the client, is allowed.
+    ///
+    /// `*` allows any origin, and `null` only allows `null`. Otherwise the
+    /// origin has to match, comparing scheme and host case-insensitively.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::AccessControlAllowOrigin;
+    /// use std::convert::TryFrom;
+    ///
+    /// let allow_origin = AccessControlAllowOrigin::try_from("https://example.com").unwrap();
+    /// assert!(allow_origin.allows("https://EXAMPLE.com"));
+    /// assert!(!allow_origin.allows("https://example.com:8443"));
+    /// assert!(AccessControlAllowOrigin::ANY.allows("https://example.com"));
+    /// ```
+    pub fn allows(&self, origin: &str) -> bool {
+        match self.0 {
+            OriginOrAny::Any => true,
+            OriginOrAny::Origin(ref allowed) => HeaderValue::from_str(origin)
+                .ok()
+                .and_then(|value| Origin::try_from_value(&value))
+                .map(|origin| origin == *allowed)
+                .unwrap_or(false),
+        }
+    }
+}
+
+impl From<Origin> for AccessControlAllowOrigin {
+    /// Echo the request's `Origin` as the allowed origin.
+    fn from(origin: Origin) -> Self {
+        AccessControlAllowOrigin(OriginOrAny::Origin(origin))
+    }
 }
 
 impl TryFrom<&str> for AccessControlAllowOrigin {
@@ -159,6 +199,38 @@ mod tests {
         assert_eq!(headers["access-control-allow-origin"], "*");
     }
 
+    #[test]
+    fn allows_origin() {
+        let allow_origin =
+            AccessControlAllowOrigin::try_from("http://web-platform.test:8000").unwrap();
+
+        assert!(allow_origin.allows("http://web-platform.test:8000"));
+        assert!(allow_origin.allows("HTTP://Web-Platform.Test:8000"));
+        assert!(!allow_origin.allows("http://web-platform.test"));
+        assert!(!allow_origin.allows("https://web-platform.test:8000"));
+        assert!(!allow_origin.allows("null"));
+        assert!(!allow_origin.allows("not an origin"));
+    }
+
+    #[test]
+    fn allows_any() {
+        assert!(AccessControlAllowOrigin::ANY.allows("http://web-platform.test:8000"));
+        assert!(AccessControlAllowOrigin::ANY.allows("null"));
+    }
+
+    #[test]
+    fn allows_null() {
+        assert!(AccessControlAllowOrigin::NULL.allows("null"));
+        assert!(!AccessControlAllowOrigin::NULL.allows("http://web-platform.test"));
+    }
+
+    #[test]
+    fn from_origin() {
+        let origin = test_decode::<Origin>(&["https://example.com"]).unwrap();
+        let allow_origin = AccessControlAllowOrigin::from(origin.clone());
+        assert_eq!(allow_origin.origin(), Some(&origin));
+    }
+
     #[test]
     fn null() {
         let allow_origin = test_decode::<AccessControlAllowOrigin>(&["null"]).unwrap();
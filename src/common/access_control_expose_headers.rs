@@ -18,6 +18,7 @@ use crate::util::FlatCsv;
 ///
 /// # Example values
 /// * `ETag, Content-Length`
+/// * `*`
 ///
 /// # Examples
 ///
@@ -41,6 +42,19 @@ derive_header! {
 }
 
 impl AccessControlExposeHeaders {
+    /// Create an `Access-Control-Expose-Headers: *` header, exposing all
+    /// headers.
+    ///
+    /// Browsers only honor `*` for requests without credentials.
+    pub fn any() -> Self {
+        AccessControlExposeHeaders(HeaderValue::from_static("*").into())
+    }
+
+    /// Check if this is `Access-Control-Expose-Headers: *`.
+    pub fn is_any(&self) -> bool {
+        self.0.iter().any(|s| s == "*")
+    }
+
     /// Returns an iterator over `HeaderName`s contained within.
     pub fn iter(&self) -> impl Iterator<Item = HeaderName> + '_ {
         self.0.iter().filter_map(|s| s.parse().ok())
@@ -72,6 +86,31 @@ mod tests {
         assert_eq!(as_vec[1], "bar");
     }
 
+    #[test]
+    fn roundtrip() {
+        let expose_headers =
+            test_decode::<AccessControlExposeHeaders>(&["Content-Length, X-Request-Id"]).unwrap();
+
+        let names = expose_headers.iter().collect::<Vec<_>>();
+        assert_eq!(names, ["content-length", "x-request-id"]);
+        assert!(!expose_headers.is_any());
+
+        let headers = test_encode(names.into_iter().collect::<AccessControlExposeHeaders>());
+        assert_eq!(
+            headers["access-control-expose-headers"],
+            "content-length, x-request-id"
+        );
+    }
+
+    #[test]
+    fn any() {
+        let expose_headers = test_decode::<AccessControlExposeHeaders>(&["*"]).unwrap();
+        assert!(expose_headers.is_any());
+
+        let headers = test_encode(AccessControlExposeHeaders::any());
+        assert_eq!(headers["access-control-expose-headers"], "*");
+    }
+
     #[test]
     fn from_iter() {
         let expose: AccessControlExposeHeaders =
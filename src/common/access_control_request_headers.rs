@@ -46,6 +46,14 @@ impl AccessControlRequestHeaders {
     pub fn iter(&self) -> impl Iterator<Item = HeaderName> + '_ {
         self.0.iter().filter_map(|s| s.parse().ok())
     }
+
+    /// Check if the given header is one the actual request will use.
+    ///
+    /// Unlike in `Access-Control-Allow-Headers`, `*` has no special meaning
+    /// here.
+    pub fn contains(&self, name: &HeaderName) -> bool {
+        self.0.iter().any(|s| s.eq_ignore_ascii_case(name.as_str()))
+    }
 }
 
 impl FromIterator<HeaderName> for AccessControlRequestHeaders {
@@ -73,6 +81,16 @@ mod tests {
         assert_eq!(as_vec[1], "bar");
     }
 
+    #[test]
+    fn contains() {
+        let req_headers =
+            test_decode::<AccessControlRequestHeaders>(&["Content-Type, x-custom"]).unwrap();
+
+        assert!(req_headers.contains(&::http::header::CONTENT_TYPE));
+        assert!(req_headers.contains(&HeaderName::from_static("x-custom")));
+        assert!(!req_headers.contains(&::http::header::ACCEPT));
+    }
+
     #[test]
     fn from_iter() {
         let req_headers: AccessControlRequestHeaders =
@@ -1,5 +1,6 @@
 use http::{HeaderName, HeaderValue, Method};
 
+use crate::util::{method_set, IterExt};
 use crate::{Error, Header};
 
 /// `Access-Control-Request-Method` header, part of
@@ -16,6 +17,8 @@ use crate::{Error, Header};
 /// # Example values
 /// * `GET`
 ///
+/// Only a single method is allowed, so a list like `GET, POST` is invalid.
+///
 /// # Examples
 ///
 /// ```
@@ -35,28 +38,15 @@ impl Header for AccessControlRequestMethod {
 
     fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, Error> {
         values
-            .next()
-            .and_then(|value| Method::from_bytes(value.as_bytes()).ok())
+            .just_one()
+            .and_then(|value| value.to_str().ok())
+            .and_then(method_set::parse_method)
             .map(AccessControlRequestMethod)
             .ok_or_else(Error::invalid)
     }
 
     fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
-        // For the more common methods, try to use a static string.
-        let s = match self.0 {
-            Method::GET => "GET",
-            Method::POST => "POST",
-            Method::PUT => "PUT",
-            Method::DELETE => "DELETE",
-            _ => {
-                let val = HeaderValue::from_str(self.0.as_ref())
-                    .expect("Methods are also valid HeaderValues");
-                values.extend(::std::iter::once(val));
-                return;
-            }
-        };
-
-        values.extend(::std::iter::once(HeaderValue::from_static(s)));
+        values.extend(::std::iter::once(method_set::to_value(&self.0)));
     }
 }
 
@@ -71,3 +61,41 @@ impl From<AccessControlRequestMethod> for Method {
         method.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn single_method() {
+        let req_method = test_decode::<AccessControlRequestMethod>(&["POST"]).unwrap();
+        assert_eq!(Method::from(req_method.clone()), Method::POST);
+
+        let headers = test_encode(req_method);
+        assert_eq!(headers["access-control-request-method"], "POST");
+    }
+
+    #[test]
+    fn rejects_list() {
+        assert_eq!(
+            test_decode::<AccessControlRequestMethod>(&["GET, POST"]),
+            None
+        );
+        assert_eq!(
+            test_decode::<AccessControlRequestMethod>(&["GET", "POST"]),
+            None
+        );
+    }
+
+    #[test]
+    fn decode_case_insensitive() {
+        assert_eq!(
+            test_decode::<AccessControlRequestMethod>(&["delete"]),
+            Some(AccessControlRequestMethod(Method::DELETE))
+        );
+
+        let headers = test_encode(AccessControlRequestMethod::from(Method::PATCH));
+        assert_eq!(headers["access-control-request-method"], "PATCH");
+    }
+}
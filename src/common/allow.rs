@@ -1,8 +1,8 @@
 use std::iter::FromIterator;
 
-use http::{HeaderValue, Method};
+use http::Method;
 
-use crate::util::FlatCsv;
+use crate::util::MethodSet;
 
 /// `Allow` header, defined in [RFC7231](https://datatracker.ietf.org/doc/html/rfc7231#section-7.4.1)
 ///
@@ -34,7 +34,7 @@ use crate::util::FlatCsv;
 ///     .collect::<Allow>();
 /// ```
 #[derive(Clone, Debug, PartialEq)]
-pub struct Allow(FlatCsv);
+pub struct Allow(MethodSet);
 
 derive_header! {
     Allow(_),
@@ -44,7 +44,7 @@ derive_header! {
 impl Allow {
     /// Returns an iterator over `Method`s contained within.
     pub fn iter(&self) -> impl Iterator<Item = Method> + '_ {
-        self.0.iter().filter_map(|s| s.parse().ok())
+        self.0.iter().cloned()
     }
 }
 
@@ -53,15 +53,35 @@ impl FromIterator<Method> for Allow {
     where
         I: IntoIterator<Item = Method>,
     {
-        let flat = iter
-            .into_iter()
-            .map(|method| {
-                method
-                    .as_str()
-                    .parse::<HeaderValue>()
-                    .expect("Method is a valid HeaderValue")
-            })
-            .collect();
-        Allow(flat)
+        Allow(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn dedup_case_insensitive() {
+        let allow = test_decode::<Allow>(&["GET, head, get", "HEAD, fOObAr"]).unwrap();
+
+        assert_eq!(
+            allow.iter().collect::<Vec<_>>(),
+            vec![
+                Method::GET,
+                Method::HEAD,
+                Method::from_bytes(b"fOObAr").unwrap()
+            ]
+        );
+
+        let headers = test_encode(allow);
+        assert_eq!(headers["allow"], "GET, HEAD, fOObAr");
+    }
+
+    #[test]
+    fn empty() {
+        let allow = test_decode::<Allow>(&[""]).unwrap();
+        assert_eq!(allow.iter().count(), 0);
     }
 }
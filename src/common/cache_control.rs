@@ -1,12 +1,12 @@
 use std::fmt;
 use std::iter::FromIterator;
 use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use http::{HeaderName, HeaderValue};
 
 use crate::util::{self, csv, Seconds};
-use crate::{Error, Header};
+use crate::{Error, Expires, Header};
 
 /// `Cache-Control` header, defined in [RFC7234](https://tools.ietf.org/html/rfc7234#section-5.2)
 /// with extensions in [RFC8246](https://www.rfc-editor.org/rfc/rfc8246)
@@ -77,6 +77,7 @@ impl Flags {
 
 impl CacheControl {
     /// Construct a new empty `CacheControl` header.
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         CacheControl {
             flags: Flags::empty(),
@@ -129,6 +130,11 @@ impl CacheControl {
         self.flags.contains(Flags::MUST_REVALIDATE)
     }
 
+    /// Check if the `proxy-revalidate` directive is set.
+    pub fn proxy_revalidate(&self) -> bool {
+        self.flags.contains(Flags::PROXY_REVALIDATE)
+    }
+
     /// Check if the `must-understand` directive is set.
     pub fn must_understand(&self) -> bool {
         self.flags.contains(Flags::MUST_UNDERSTAND)
@@ -204,6 +210,12 @@ impl CacheControl {
         self
     }
 
+    /// Set the `proxy-revalidate` directive.
+    pub fn with_proxy_revalidate(mut self) -> Self {
+        self.flags.insert(Flags::PROXY_REVALIDATE);
+        self
+    }
+
     /// Set the `must-understand` directive.
     pub fn with_must_understand(mut self) -> Self {
         self.flags.insert(Flags::MUST_UNDERSTAND);
@@ -235,6 +247,54 @@ impl CacheControl {
     }
 }
 
+/// Compute the freshness lifetime of a response, as defined in
+/// [RFC7234](https://tools.ietf.org/html/rfc7234#section-4.2.1).
+///
+/// The first of these that is present is used:
+///
+/// 1. the `s-maxage` directive of `cache_control`,
+/// 2. the `max-age` directive of `cache_control`,
+/// 3. `expires` minus `date`, the value of the response's `Date` header.
+///
+/// An `Expires` at or before `date` gives a lifetime of zero. `None` means
+/// there is no explicit lifetime, so a cache would have to use a heuristic.
+///
+/// Note that `s-maxage` only applies to shared caches, so private caches
+/// should leave it out.
+///
+/// # Example
+///
+/// ```
+/// use std::time::{Duration, SystemTime};
+/// use headers::{freshness_lifetime, CacheControl, Expires};
+///
+/// let date = SystemTime::now();
+/// let cc = CacheControl::new().with_max_age(Duration::from_secs(60));
+/// let expires = Expires::from(date + Duration::from_secs(3600));
+///
+/// assert_eq!(
+///     freshness_lifetime(Some(&cc), Some(&expires), date),
+///     Some(Duration::from_secs(60)),
+/// );
+/// ```
+pub fn freshness_lifetime(
+    cache_control: Option<&CacheControl>,
+    expires: Option<&Expires>,
+    date: SystemTime,
+) -> Option<Duration> {
+    if let Some(cc) = cache_control {
+        if let Some(lifetime) = cc.s_max_age().or_else(|| cc.max_age()) {
+            return Some(lifetime);
+        }
+    }
+
+    expires.map(|&expires| {
+        SystemTime::from(expires)
+            .duration_since(date)
+            .unwrap_or_default()
+    })
+}
+
 impl Header for CacheControl {
     fn name() -> &'static HeaderName {
         &::http::header::CACHE_CONTROL
@@ -459,6 +519,72 @@ mod tests {
     use super::super::{test_decode, test_encode};
     use super::*;
 
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn freshness_lifetime_s_max_age() {
+        let date = SystemTime::UNIX_EPOCH + secs(1_000_000);
+        let cc = CacheControl::new()
+            .with_max_age(secs(60))
+            .with_s_max_age(secs(30));
+        let expires = Expires::from(date + secs(3600));
+
+        assert_eq!(
+            freshness_lifetime(Some(&cc), Some(&expires), date),
+            Some(secs(30))
+        );
+    }
+
+    #[test]
+    fn freshness_lifetime_max_age() {
+        let date = SystemTime::UNIX_EPOCH + secs(1_000_000);
+        let cc = CacheControl::new().with_max_age(secs(60));
+        let expires = Expires::from(date + secs(3600));
+
+        assert_eq!(
+            freshness_lifetime(Some(&cc), Some(&expires), date),
+            Some(secs(60))
+        );
+    }
+
+    #[test]
+    fn freshness_lifetime_expires() {
+        let date = SystemTime::UNIX_EPOCH + secs(1_000_000);
+        let cc = CacheControl::new().with_no_cache();
+        let expires = Expires::from(date + secs(3600));
+
+        assert_eq!(
+            freshness_lifetime(Some(&cc), Some(&expires), date),
+            Some(secs(3600))
+        );
+        assert_eq!(
+            freshness_lifetime(None, Some(&expires), date),
+            Some(secs(3600))
+        );
+    }
+
+    #[test]
+    fn freshness_lifetime_expired() {
+        let date = SystemTime::UNIX_EPOCH + secs(1_000_000);
+        let expires = Expires::from(date - secs(3600));
+
+        assert_eq!(
+            freshness_lifetime(None, Some(&expires), date),
+            Some(secs(0))
+        );
+    }
+
+    #[test]
+    fn freshness_lifetime_none() {
+        let date = SystemTime::UNIX_EPOCH + secs(1_000_000);
+        let cc = CacheControl::new().with_public();
+
+        assert_eq!(freshness_lifetime(Some(&cc), None, date), None);
+        assert_eq!(freshness_lifetime(None, None, date), None);
+    }
+
     #[test]
     fn test_parse_multiple_headers() {
         assert_eq!(
@@ -524,6 +650,36 @@ mod tests {
         assert!(cc.must_revalidate());
     }
 
+    #[test]
+    fn test_proxy_revalidate() {
+        let cc = CacheControl::new().with_proxy_revalidate();
+        let headers = test_encode(cc.clone());
+        assert_eq!(headers["cache-control"], "proxy-revalidate");
+        assert_eq!(
+            test_decode::<CacheControl>(&["proxy-revalidate"]).unwrap(),
+            cc
+        );
+        assert!(cc.proxy_revalidate());
+        assert!(!cc.must_revalidate());
+    }
+
+    #[test]
+    fn test_parse_public_immutable() {
+        let cc = test_decode::<CacheControl>(&["public, max-age=31536000, immutable"]).unwrap();
+        assert!(cc.public());
+        assert!(cc.immutable());
+        assert!(!cc.private());
+        assert!(!cc.no_transform());
+        assert_eq!(cc.max_age(), Some(Duration::from_secs(31_536_000)));
+        assert_eq!(
+            cc,
+            CacheControl::new()
+                .with_public()
+                .with_max_age(Duration::from_secs(31_536_000))
+                .with_immutable()
+        );
+    }
+
     #[test]
     fn test_must_understand() {
         let cc = CacheControl::new().with_must_understand();
@@ -88,8 +88,59 @@ impl Connection {
         let s = name.as_connection_option();
         self.0.iter().any(|opt| opt.eq_ignore_ascii_case(s))
     }
+
+    /// Get the headers a proxy must remove before forwarding the message.
+    ///
+    /// These are the headers named by the connection options, followed by
+    /// the standard hop-by-hop headers of
+    /// [RFC9110](https://www.rfc-editor.org/rfc/rfc9110#section-7.6.1):
+    /// `Connection`, `Keep-Alive`, `Proxy-Connection`, `TE`,
+    /// `Transfer-Encoding` and `Upgrade`. The `close` option, and options
+    /// that aren't valid header names, are skipped. Each header is only
+    /// yielded once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{Connection, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("connection", "close, X-Custom".parse().unwrap());
+    /// headers.insert("x-custom", "1".parse().unwrap());
+    /// headers.insert("transfer-encoding", "chunked".parse().unwrap());
+    ///
+    /// let conn = headers.typed_get::<Connection>().unwrap();
+    /// for name in conn.hop_by_hop_headers() {
+    ///     headers.remove(name);
+    /// }
+    /// assert!(headers.is_empty());
+    /// ```
+    pub fn hop_by_hop_headers(&self) -> impl Iterator<Item = HeaderName> + '_ {
+        let named = self
+            .0
+            .iter()
+            .filter(|opt| !opt.eq_ignore_ascii_case("close"))
+            .filter_map(|opt| HeaderName::from_bytes(opt.as_bytes()).ok());
+
+        let mut names = Vec::new();
+        for name in named.chain(HOP_BY_HOP.iter().cloned()) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names.into_iter()
+    }
 }
 
+static HOP_BY_HOP: [HeaderName; 6] = [
+    ::http::header::CONNECTION,
+    HeaderName::from_static("keep-alive"),
+    HeaderName::from_static("proxy-connection"),
+    ::http::header::TE,
+    ::http::header::TRANSFER_ENCODING,
+    ::http::header::UPGRADE,
+];
+
 impl FromIterator<HeaderName> for Connection {
     fn from_iter<I>(iter: I) -> Self
     where
@@ -100,6 +151,41 @@ impl FromIterator<HeaderName> for Connection {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::super::test_decode;
+    use super::*;
+
+    #[test]
+    fn hop_by_hop_headers() {
+        let conn = test_decode::<Connection>(&["close, X-Custom"]).unwrap();
+        let names = conn.hop_by_hop_headers().collect::<Vec<_>>();
+
+        assert_eq!(names[0], "x-custom");
+        assert!(names.contains(&::http::header::TRANSFER_ENCODING));
+        assert!(!names.iter().any(|name| name == "close"));
+    }
+
+    #[test]
+    fn hop_by_hop_headers_dedup() {
+        let conn = test_decode::<Connection>(&["Upgrade, keep-alive, x-a, X-A"]).unwrap();
+        let names = conn.hop_by_hop_headers().collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            [
+                "upgrade",
+                "keep-alive",
+                "x-a",
+                "connection",
+                "proxy-connection",
+                "te",
+                "transfer-encoding",
+            ]
+        );
+    }
+}
+
 mod sealed {
     use http::HeaderName;
 
@@ -0,0 +1,169 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use http::{HeaderName, HeaderValue};
+
+use crate::util::sf;
+use crate::{Error, Header};
+
+static CONTENT_DIGEST: HeaderName = HeaderName::from_static("content-digest");
+
+/// `Content-Digest` header, defined in
+/// [RFC9530](https://www.rfc-editor.org/rfc/rfc9530#section-2)
+///
+/// The `Content-Digest` header field contains one or more digests of the
+/// message content, each keyed by its hashing algorithm.
+///
+/// # ABNF
+///
+/// The value is a structured fields
+/// [dictionary](https://www.rfc-editor.org/rfc/rfc8941#section-3.2), where
+/// every digest is a byte sequence, base64-encoded between colons.
+///
+/// ```text
+/// Content-Digest = sf-dictionary
+/// ```
+///
+/// # Example values
+/// * `sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:`
+/// * `sha-512=:WZDPaVn/7XgHaAy8pmojAkGWoRx2UFChF41A2svX+TaPm+AbwAgBWnrIiYllu7BNNyealdVLvRwEmTHWXvJwew==:, sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:`
+///
+/// # Examples
+///
+/// ```
+/// use headers::ContentDigest;
+///
+/// let digest = ContentDigest::new("sha-256", b"not really a digest").unwrap();
+/// assert_eq!(digest.get("sha-256"), Some(b"not really a digest".to_vec()));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ContentDigest(Vec<(String, Vec<u8>)>);
+
+error_type!(InvalidContentDigest);
+
+impl ContentDigest {
+    /// Create a `Content-Digest` with a single digest.
+    ///
+    /// The `algorithm` must be a valid structured fields key, such as
+    /// `sha-256` or `sha-512`.
+    pub fn new(algorithm: &str, digest: &[u8]) -> Result<Self, InvalidContentDigest> {
+        if !sf::is_key(algorithm) {
+            return Err(InvalidContentDigest { _inner: () });
+        }
+        Ok(ContentDigest(vec![(algorithm.to_owned(), digest.to_vec())]))
+    }
+
+    /// Get the digest computed with the given `algorithm`.
+    pub fn get(&self, algorithm: &str) -> Option<Vec<u8>> {
+        self.iter()
+            .find(|&(algo, _)| algo.eq_ignore_ascii_case(algorithm))
+            .map(|(_, digest)| digest.to_vec())
+    }
+
+    /// Iterate the `(algorithm, digest)` pairs, in the order they were sent.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.0
+            .iter()
+            .map(|(algo, digest)| (algo.as_str(), digest.as_slice()))
+    }
+}
+
+impl Header for ContentDigest {
+    fn name() -> &'static HeaderName {
+        &CONTENT_DIGEST
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, Error> {
+        let joined = values
+            .map(|value| value.to_str().map_err(|_| Error::invalid()))
+            .collect::<Result<Vec<_>, _>>()?
+            .join(", ");
+
+        // Parameters may follow the byte sequence, but none are defined for
+        // digests, so they are ignored.
+        let digests = sf::parse_dictionary(&joined)
+            .ok_or_else(Error::invalid)?
+            .into_iter()
+            .map(|(algo, member)| match member {
+                sf::Member::Item(sf::Item {
+                    bare: sf::BareItem::ByteSequence(digest),
+                    ..
+                }) => Ok((algo, digest)),
+                _ => Err(Error::invalid()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if digests.is_empty() {
+            Err(Error::invalid())
+        } else {
+            Ok(ContentDigest(digests))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let s = self
+            .iter()
+            .map(|(algo, digest)| format!("{}=:{}:", algo, STANDARD.encode(digest)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let value = HeaderValue::from_str(&s).expect("Content-Digest is a valid HeaderValue");
+        values.extend(::std::iter::once(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    // The SHA-256 of `{"hello": "world"}`, from RFC9530 Appendix B.
+    const SHA_256: &str = "X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=";
+
+    #[test]
+    fn decode_sha_256() {
+        let digest = test_decode::<ContentDigest>(&[&format!("sha-256=:{}:", SHA_256)]).unwrap();
+
+        let expected = STANDARD.decode(SHA_256).unwrap();
+        assert_eq!(expected.len(), 32);
+        assert_eq!(digest.get("sha-256"), Some(expected));
+        assert_eq!(digest.get("sha-512"), None);
+    }
+
+    #[test]
+    fn decode_multiple() {
+        let digest =
+            test_decode::<ContentDigest>(&["sha-512=:AAEC:, sha-256=:AwQF:;x", "md5=:Bgc=:"])
+                .unwrap();
+
+        assert_eq!(
+            digest.iter().collect::<Vec<_>>(),
+            vec![
+                ("sha-512", &[0, 1, 2][..]),
+                ("sha-256", &[3, 4, 5][..]),
+                ("md5", &[6, 7][..]),
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ContentDigest>(&["sha-256=AAEC"]), None);
+        assert_eq!(
+            test_decode::<ContentDigest>(&["sha-256=:not base64:"]),
+            None
+        );
+        assert_eq!(test_decode::<ContentDigest>(&["SHA-256=:AAEC:"]), None);
+        assert_eq!(test_decode::<ContentDigest>(&[""]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let digest = ContentDigest::new("sha-256", &[0, 1, 2]).unwrap();
+
+        let headers = test_encode(digest);
+        assert_eq!(headers["content-digest"], "sha-256=:AAEC:");
+    }
+
+    #[test]
+    fn new_invalid_algorithm() {
+        assert!(ContentDigest::new("SHA 256", &[]).is_err());
+    }
+}
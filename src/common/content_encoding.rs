@@ -1,7 +1,10 @@
-use http::HeaderValue;
+use std::iter::FromIterator;
+
+use http::{HeaderName, HeaderValue};
 
 use self::sealed::AsCoding;
-use crate::util::FlatCsv;
+use crate::util::{reject_trailers, FlatCsv};
+use crate::{Encoding, Error, Header};
 
 /// `Content-Encoding` header, defined in
 /// [RFC7231](https://datatracker.ietf.org/doc/html/rfc7231#section-3.1.2.2)
@@ -36,9 +39,20 @@ use crate::util::FlatCsv;
 #[derive(Clone, Debug)]
 pub struct ContentEncoding(FlatCsv);
 
-derive_header! {
-    ContentEncoding(_),
-    name: CONTENT_ENCODING
+impl Header for ContentEncoding {
+    fn name() -> &'static HeaderName {
+        &::http::header::CONTENT_ENCODING
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, Error> {
+        let flat = values.collect::<FlatCsv>();
+        reject_trailers(flat.iter())?;
+        Ok(ContentEncoding(flat))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
 }
 
 impl ContentEncoding {
@@ -60,6 +74,32 @@ impl ContentEncoding {
         ContentEncoding(HeaderValue::from_static("zstd").into())
     }
 
+    /// Iterate the codings in the order they were applied.
+    pub fn encode_order(&self) -> impl Iterator<Item = Encoding> + '_ {
+        self.0.iter().filter_map(|s| s.parse().ok())
+    }
+
+    /// Iterate the codings in the order they have to be decoded, which is
+    /// the reverse of [`encode_order`](ContentEncoding::encode_order).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{ContentEncoding, Encoding};
+    ///
+    /// let content_enc = vec![Encoding::Gzip, Encoding::Brotli]
+    ///     .into_iter()
+    ///     .collect::<ContentEncoding>();
+    ///
+    /// assert_eq!(
+    ///     content_enc.decode_order().collect::<Vec<_>>(),
+    ///     vec![Encoding::Brotli, Encoding::Gzip],
+    /// );
+    /// ```
+    pub fn decode_order(&self) -> impl Iterator<Item = Encoding> {
+        self.encode_order().collect::<Vec<_>>().into_iter().rev()
+    }
+
     /// Check if this header contains a given "coding".
     ///
     /// This can be used with these argument types:
@@ -82,6 +122,60 @@ impl ContentEncoding {
     }
 }
 
+/// Create a `Content-Encoding` from the codings in the order they were
+/// applied.
+///
+/// `identity` entries are dropped, since not applying a coding is the
+/// default and `identity` should not be sent in `Content-Encoding`. So are
+/// `trailers` entries, which are not a coding, and encodings that aren't
+/// valid tokens. Only if nothing else is left, the header is `identity`.
+/// This differs from [`AcceptEncoding`](crate::AcceptEncoding), where
+/// `identity` is meaningful and always kept.
+///
+/// # Example
+///
+/// ```
+/// use headers::{ContentEncoding, Encoding};
+///
+/// let content_enc = vec![Encoding::Identity, Encoding::Gzip]
+///     .into_iter()
+///     .collect::<ContentEncoding>();
+///
+/// assert!(content_enc.contains("gzip"));
+/// assert!(!content_enc.contains("identity"));
+/// ```
+impl FromIterator<Encoding> for ContentEncoding {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Encoding>,
+    {
+        let mut values = iter
+            .into_iter()
+            .filter(|encoding| {
+                encoding.is_token()
+                    && *encoding != Encoding::Identity
+                    && *encoding != Encoding::Trailers
+            })
+            .map(HeaderValue::from)
+            .peekable();
+
+        if values.peek().is_none() {
+            return ContentEncoding(HeaderValue::from_static("identity").into());
+        }
+        ContentEncoding(values.collect())
+    }
+}
+
+/// Create a `Content-Encoding` with a single coding.
+///
+/// Like collecting, an `Encoding::Identity` creates `Content-Encoding:
+/// identity`, which shouldn't be sent. Leave out the header instead.
+impl From<Encoding> for ContentEncoding {
+    fn from(encoding: Encoding) -> ContentEncoding {
+        ::std::iter::once(encoding).collect()
+    }
+}
+
 mod sealed {
     pub trait AsCoding: Sealed {}
 
@@ -97,3 +191,75 @@ mod sealed {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn from_encoding() {
+        let headers = test_encode(ContentEncoding::from(Encoding::Zstd));
+        assert_eq!(headers["content-encoding"], "zstd");
+    }
+
+    #[test]
+    fn encode_and_decode_order() {
+        let content_enc = test_decode::<ContentEncoding>(&["gzip, br"]).unwrap();
+        assert_eq!(
+            content_enc.decode_order().collect::<Vec<_>>(),
+            vec![Encoding::Brotli, Encoding::Gzip]
+        );
+        assert_eq!(
+            content_enc.encode_order().collect::<Vec<_>>(),
+            vec![Encoding::Gzip, Encoding::Brotli]
+        );
+
+        let content_enc = test_decode::<ContentEncoding>(&["deflate", "zstd"]).unwrap();
+        assert_eq!(
+            content_enc.decode_order().collect::<Vec<_>>(),
+            vec![Encoding::Zstd, Encoding::Deflate]
+        );
+    }
+
+    #[test]
+    fn trailers_is_invalid() {
+        assert!(test_decode::<ContentEncoding>(&["gzip, trailers"]).is_none());
+
+        let content_enc = vec![Encoding::Trailers, Encoding::Gzip]
+            .into_iter()
+            .collect::<ContentEncoding>();
+        let headers = test_encode(content_enc);
+        assert_eq!(headers["content-encoding"], "gzip");
+    }
+
+    #[test]
+    fn from_iter_drops_identity() {
+        let content_enc = vec![Encoding::Identity, Encoding::Gzip]
+            .into_iter()
+            .collect::<ContentEncoding>();
+
+        let headers = test_encode(content_enc);
+        assert_eq!(headers["content-encoding"], "gzip");
+    }
+
+    #[test]
+    fn from_iter_keeps_order() {
+        let content_enc = vec![Encoding::Deflate, Encoding::Identity, Encoding::Brotli]
+            .into_iter()
+            .collect::<ContentEncoding>();
+
+        let headers = test_encode(content_enc);
+        assert_eq!(headers["content-encoding"], "deflate, br");
+    }
+
+    #[test]
+    fn from_iter_only_identity() {
+        let content_enc = vec![Encoding::Identity]
+            .into_iter()
+            .collect::<ContentEncoding>();
+
+        let headers = test_encode(content_enc);
+        assert_eq!(headers["content-encoding"], "identity");
+    }
+}
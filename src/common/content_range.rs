@@ -1,5 +1,6 @@
 use std::fmt;
 use std::ops::{Bound, RangeBounds};
+use std::str::FromStr;
 
 use http::{HeaderName, HeaderValue};
 
@@ -108,38 +109,7 @@ impl Header for ContentRange {
         values
             .next()
             .and_then(|v| v.to_str().ok())
-            .and_then(|s| split_in_two(s, ' '))
-            .and_then(|(unit, spec)| {
-                if unit != "bytes" {
-                    // For now, this only supports bytes-content-range. nani?
-                    return None;
-                }
-
-                let (range, complete_length) = split_in_two(spec, '/')?;
-
-                let complete_length = if complete_length == "*" {
-                    None
-                } else {
-                    Some(complete_length.parse().ok()?)
-                };
-
-                let range = if range == "*" {
-                    None
-                } else {
-                    let (first_byte, last_byte) = split_in_two(range, '-')?;
-                    let first_byte = first_byte.parse().ok()?;
-                    let last_byte = last_byte.parse().ok()?;
-                    if last_byte < first_byte {
-                        return None;
-                    }
-                    Some((first_byte, last_byte))
-                };
-
-                Some(ContentRange {
-                    range,
-                    complete_length,
-                })
-            })
+            .and_then(|s| s.parse().ok())
             .ok_or_else(Error::invalid)
     }
 
@@ -170,6 +140,62 @@ impl Header for ContentRange {
     }
 }
 
+/// Parses a `bytes` content range.
+///
+/// Besides the fully specified `bytes 0-499/1234`, this accepts an unknown
+/// complete length (`bytes 0-499/*`) and an unsatisfied range
+/// (`bytes */1234`), which can be told apart with
+/// [`bytes_range`](ContentRange::bytes_range) and
+/// [`bytes_len`](ContentRange::bytes_len). A range ending before it
+/// starts, or past the complete length, is invalid.
+impl FromStr for ContentRange {
+    type Err = InvalidContentRange;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s).ok_or(InvalidContentRange { _inner: () })
+    }
+}
+
+fn parse(s: &str) -> Option<ContentRange> {
+    let (unit, spec) = split_in_two(s, ' ')?;
+    if unit != "bytes" {
+        // For now, this only supports bytes-content-range. nani?
+        return None;
+    }
+
+    let (range, complete_length) = split_in_two(spec, '/')?;
+
+    let complete_length = if complete_length == "*" {
+        None
+    } else {
+        Some(complete_length.parse().ok()?)
+    };
+
+    let range = if range == "*" {
+        // An unsatisfied range needs the complete length.
+        complete_length?;
+        None
+    } else {
+        let (first_byte, last_byte) = split_in_two(range, '-')?;
+        let first_byte = first_byte.parse().ok()?;
+        let last_byte = last_byte.parse().ok()?;
+        if last_byte < first_byte {
+            return None;
+        }
+        if let Some(len) = complete_length {
+            if last_byte >= len {
+                return None;
+            }
+        }
+        Some((first_byte, last_byte))
+    };
+
+    Some(ContentRange {
+        range,
+        complete_length,
+    })
+}
+
 fn split_in_two(s: &str, separator: char) -> Option<(&str, &str)> {
     let mut iter = s.splitn(2, separator);
     match (iter.next(), iter.next()) {
@@ -236,3 +262,67 @@ test_header!(test_bytes_unknown_range,
             vec![b"bytes 1-2-3/500"],
             None::<ContentRange>);
 */
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn bytes() {
+        let cr = test_decode::<ContentRange>(&["bytes 0-499/1234"]).unwrap();
+        assert_eq!(cr.bytes_range(), Some((0, 499)));
+        assert_eq!(cr.bytes_len(), Some(1234));
+    }
+
+    #[test]
+    fn bytes_unknown_len() {
+        let cr = test_decode::<ContentRange>(&["bytes 0-499/*"]).unwrap();
+        assert_eq!(cr.bytes_range(), Some((0, 499)));
+        assert_eq!(cr.bytes_len(), None);
+    }
+
+    #[test]
+    fn bytes_unsatisfied() {
+        let cr = test_decode::<ContentRange>(&["bytes */1234"]).unwrap();
+        assert_eq!(cr, ContentRange::unsatisfied_bytes(1234));
+        assert_eq!(cr.bytes_range(), None);
+        assert_eq!(cr.bytes_len(), Some(1234));
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            "bytes 0-499/*".parse::<ContentRange>().unwrap(),
+            ContentRange::bytes(0..500, None).unwrap()
+        );
+        assert!("bytes 500-499/1000".parse::<ContentRange>().is_err());
+    }
+
+    #[test]
+    fn malformed() {
+        for s in &[
+            "bytes 500-499/1000",
+            "bytes 0-1000/1000",
+            "bytes */*",
+            "bytes 0-499",
+            "bytes",
+            "",
+            "bytes 1-2/500 3",
+            "bytes 1-2/500/600",
+            "bytes 1-2-3/500",
+            "seconds 1-2/500",
+        ] {
+            assert_eq!(test_decode::<ContentRange>(&[s]), None, "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(ContentRange::bytes(0..500, None).unwrap());
+        assert_eq!(headers["content-range"], "bytes 0-499/*");
+
+        let headers = test_encode(ContentRange::unsatisfied_bytes(1234));
+        assert_eq!(headers["content-range"], "bytes */1234");
+    }
+}
@@ -94,6 +94,177 @@ impl ContentType {
     pub fn octet_stream() -> ContentType {
         ContentType(mime::APPLICATION_OCTET_STREAM)
     }
+
+    /// Get the `charset` parameter, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::ContentType;
+    ///
+    /// assert_eq!(ContentType::text_utf8().charset(), Some("utf-8"));
+    /// assert_eq!(ContentType::json().charset(), None);
+    /// ```
+    pub fn charset(&self) -> Option<&str> {
+        self.0
+            .get_param(mime::CHARSET)
+            .map(|charset| charset.as_str())
+    }
+
+    /// Iterate all the parameters of the media type, as `(name, value)`.
+    ///
+    /// Quoted values are returned without their quotes.
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .params()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Get the `boundary` parameter of a multipart type, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::ContentType;
+    ///
+    /// let ct = "multipart/form-data; boundary=----abc".parse::<ContentType>().unwrap();
+    /// assert_eq!(ct.boundary(), Some("----abc"));
+    /// ```
+    pub fn boundary(&self) -> Option<&str> {
+        self.0
+            .get_param(mime::BOUNDARY)
+            .map(|boundary| boundary.as_str())
+    }
+
+    /// Check if both have the same type and subtype, ignoring parameters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::ContentType;
+    ///
+    /// assert!(ContentType::text_utf8().essence_eq(&ContentType::text()));
+    /// assert!(!ContentType::text().essence_eq(&ContentType::html()));
+    /// ```
+    pub fn essence_eq(&self, other: &ContentType) -> bool {
+        self.0
+            .essence_str()
+            .eq_ignore_ascii_case(other.0.essence_str())
+    }
+
+    /// Check if both are the same media type, regardless of how they were
+    /// written.
+    ///
+    /// The type, subtype and parameter names are compared
+    /// case-insensitively, and the order of the parameters doesn't matter.
+    /// Parameter values are case-sensitive, except for `charset`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::ContentType;
+    ///
+    /// let a = "text/HTML; Charset=UTF-8; level=1".parse::<ContentType>().unwrap();
+    /// let b = "text/html; level=1; charset=utf-8".parse::<ContentType>().unwrap();
+    /// assert_ne!(a, b);
+    /// assert!(a.semantically_eq(&b));
+    /// ```
+    pub fn semantically_eq(&self, other: &ContentType) -> bool {
+        fn sorted_params(ct: &ContentType) -> Vec<(String, &str)> {
+            let mut params = ct
+                .params()
+                .map(|(name, value)| (name.to_ascii_lowercase(), value))
+                .collect::<Vec<_>>();
+            params.sort();
+            params
+        }
+
+        let (a, b) = (sorted_params(self), sorted_params(other));
+        self.essence_eq(other)
+            && a.len() == b.len()
+            && a.iter().zip(&b).all(|((name, a), (other_name, b))| {
+                name == other_name
+                    && if name == "charset" {
+                        a.eq_ignore_ascii_case(b)
+                    } else {
+                        a == b
+                    }
+            })
+    }
+
+    /// Return this content type with the `charset` parameter set to
+    /// `charset`, replacing an existing one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `charset` is not a valid token. Use
+    /// [`ContentType::try_with_charset`] for a charset that comes from
+    /// runtime data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::ContentType;
+    ///
+    /// let ct = ContentType::html().with_charset("utf-8");
+    /// assert_eq!(ct.to_string(), "text/html; charset=utf-8");
+    /// ```
+    pub fn with_charset(self, charset: &str) -> ContentType {
+        self.try_with_charset(charset)
+            .expect("charset must be a token")
+    }
+
+    /// Return this content type with the `charset` parameter set to
+    /// `charset`, replacing an existing one.
+    ///
+    /// Returns an error if `charset` is not a valid token.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::ContentType;
+    ///
+    /// let ct = ContentType::html().try_with_charset("utf-8").unwrap();
+    /// assert_eq!(ct.to_string(), "text/html; charset=utf-8");
+    /// assert!(ContentType::html().try_with_charset("utf 8").is_err());
+    /// ```
+    pub fn try_with_charset(self, charset: &str) -> Result<ContentType, InvalidContentType> {
+        if charset.is_empty() || needs_quotes(charset) {
+            return Err(InvalidContentType { _inner: () });
+        }
+
+        let mut s = self.0.essence_str().to_owned();
+        let mut replaced = false;
+        for (name, value) in self.0.params() {
+            s.push_str("; ");
+            s.push_str(name.as_str());
+            s.push('=');
+            if name == mime::CHARSET {
+                s.push_str(charset);
+                replaced = true;
+            } else if needs_quotes(value.as_str()) {
+                s.push('"');
+                s.push_str(&value.as_str().replace('\\', "\\\\").replace('"', "\\\""));
+                s.push('"');
+            } else {
+                s.push_str(value.as_str());
+            }
+        }
+        if !replaced {
+            s.push_str("; charset=");
+            s.push_str(charset);
+        }
+
+        Ok(ContentType(s.parse().expect("charset is a valid token")))
+    }
+}
+
+error_type!(InvalidContentType);
+
+fn needs_quotes(value: &str) -> bool {
+    !value
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
 }
 
 impl Header for ContentType {
@@ -160,6 +331,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn params_and_boundary() {
+        let ct = test_decode::<ContentType>(&["multipart/form-data; boundary=----abc"]).unwrap();
+        assert_eq!(ct.boundary(), Some("----abc"));
+        assert_eq!(ct.params().collect::<Vec<_>>(), [("boundary", "----abc")]);
+
+        let ct =
+            test_decode::<ContentType>(&["text/plain; charset=utf-8; format=\"flowed\""]).unwrap();
+        assert_eq!(
+            ct.params().collect::<Vec<_>>(),
+            [("charset", "utf-8"), ("format", "flowed")]
+        );
+        assert_eq!(ct.boundary(), None);
+    }
+
+    #[test]
+    fn essence_eq() {
+        let a = test_decode::<ContentType>(&["TEXT/Plain"]).unwrap();
+        assert!(a.essence_eq(&ContentType::text()));
+        assert!(a.essence_eq(&ContentType::text_utf8()));
+        assert!(!a.essence_eq(&ContentType::html()));
+    }
+
+    #[test]
+    fn semantically_eq_type_case() {
+        let a = test_decode::<ContentType>(&["Text/HTML"]).unwrap();
+        assert!(a.semantically_eq(&ContentType::html()));
+        assert!(!a.semantically_eq(&ContentType::text()));
+    }
+
+    #[test]
+    fn semantically_eq_param_name_case() {
+        let a = test_decode::<ContentType>(&["text/plain; Charset=utf-8"]).unwrap();
+        assert!(a.semantically_eq(&ContentType::text_utf8()));
+    }
+
+    #[test]
+    fn semantically_eq_param_order() {
+        let a = test_decode::<ContentType>(&["text/html; charset=utf-8; level=1"]).unwrap();
+        let b = test_decode::<ContentType>(&["text/html; level=1; charset=utf-8"]).unwrap();
+        assert!(a.semantically_eq(&b));
+
+        let c = test_decode::<ContentType>(&["text/html; level=1"]).unwrap();
+        assert!(!a.semantically_eq(&c));
+        assert!(!c.semantically_eq(&a));
+    }
+
+    #[test]
+    fn semantically_eq_param_values() {
+        let a = test_decode::<ContentType>(&["text/plain; charset=\"UTF-8\""]).unwrap();
+        assert!(a.semantically_eq(&ContentType::text_utf8()));
+
+        let a = test_decode::<ContentType>(&["multipart/form-data; boundary=\"AbC\""]).unwrap();
+        let b = test_decode::<ContentType>(&["multipart/form-data; boundary=\"abc\""]).unwrap();
+        assert!(!a.semantically_eq(&b));
+    }
+
     #[test]
     fn from_str() {
         assert_eq!(
@@ -169,6 +397,57 @@ mod tests {
         assert!("invalid-mimetype".parse::<ContentType>().is_err());
     }
 
+    #[test]
+    fn with_charset() {
+        let ct = ContentType::text().with_charset("utf-8");
+
+        assert_eq!(ct.charset(), Some("utf-8"));
+        assert_eq!(ct.to_string(), "text/plain; charset=utf-8");
+        assert_eq!(ContentType::text().charset(), None);
+    }
+
+    #[test]
+    fn with_charset_replaces() {
+        let ct = test_decode::<ContentType>(&["text/html; charset=iso-8859-1; level=1"])
+            .unwrap()
+            .with_charset("utf-8");
+
+        assert_eq!(ct.charset(), Some("utf-8"));
+        assert_eq!(ct.to_string(), "text/html; charset=utf-8; level=1");
+    }
+
+    #[test]
+    fn with_charset_keeps_quoted_params() {
+        let ct = test_decode::<ContentType>(&["multipart/form-data; boundary=\"a b\""])
+            .unwrap()
+            .with_charset("utf-8");
+
+        assert_eq!(
+            ct.to_string(),
+            "multipart/form-data; boundary=\"a b\"; charset=utf-8"
+        );
+        assert_eq!(
+            ct.0.get_param(mime::BOUNDARY).map(|b| b.as_str()),
+            Some("a b")
+        );
+    }
+
+    #[test]
+    fn try_with_charset() {
+        let ct = ContentType::html().try_with_charset("utf-8").unwrap();
+        assert_eq!(ct.charset(), Some("utf-8"));
+
+        assert!(ContentType::html().try_with_charset("utf 8").is_err());
+        assert!(ContentType::html().try_with_charset("").is_err());
+        assert!(ContentType::html().try_with_charset("\"utf-8\"").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn with_charset_invalid() {
+        let _ = ContentType::text().with_charset("utf 8");
+    }
+
     bench_header!(bench_plain, ContentType, "text/plain");
     bench_header!(bench_json, ContentType, "application/json");
     bench_header!(
@@ -44,3 +44,5 @@ impl From<Date> for SystemTime {
         date.0.into()
     }
 }
+
+date_conversions!(Date);
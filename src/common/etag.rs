@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::util::EntityTag;
+use crate::util::{trim_ows, EntityTag};
 
 /// `ETag` header, defined in [RFC7232](https://datatracker.ietf.org/doc/html/rfc7232#section-2.3)
 ///
@@ -51,7 +51,9 @@ error_type!(InvalidETag);
 impl FromStr for ETag {
     type Err = InvalidETag;
     fn from_str(src: &str) -> Result<Self, Self::Err> {
-        let val = src.parse().map_err(|_| InvalidETag { _inner: () })?;
+        let val = trim_ows(src)
+            .parse()
+            .map_err(|_| InvalidETag { _inner: () })?;
 
         EntityTag::from_owned(val)
             .map(ETag)
@@ -110,3 +112,17 @@ test_etag {
         None::<ETag>);
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_trims() {
+        assert_eq!(
+            " \"xyzzy\"\t".parse::<ETag>().unwrap(),
+            "\"xyzzy\"".parse::<ETag>().unwrap()
+        );
+        assert!(" ".parse::<ETag>().is_err());
+    }
+}
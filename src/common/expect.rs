@@ -52,6 +52,54 @@ impl Header for Expect {
     }
 }
 
+/// What a server should do with a request that may carry an `Expect` header.
+///
+/// Returned by [`should_send_continue`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContinueDecision {
+    /// Send an interim `100 Continue`, then read the body.
+    Continue,
+    /// Respond with a final status instead of `100 Continue`, without
+    /// reading the body.
+    ///
+    /// The caller picks the status that says why the body isn't wanted,
+    /// such as `401 Unauthorized` or `413 Payload Too Large`. The expectation
+    /// itself was understood, so this isn't `417 Expectation Failed`.
+    Reject,
+    /// The client isn't waiting, so handle the request as usual.
+    Proceed,
+}
+
+/// Decide how to answer a request's `Expect` header, as defined in
+/// [RFC7231](https://datatracker.ietf.org/doc/html/rfc7231#section-5.1.1).
+///
+/// `body_acceptable` is whether the server is willing to receive the
+/// request body, for instance after checking the request headers. A client
+/// that sent `Expect: 100-continue` waits for `100 Continue` before sending
+/// the body, while a request without it can be handled directly.
+///
+/// An `Expect` value other than `100-continue` doesn't decode, and should
+/// also be answered with `417 Expectation Failed`.
+///
+/// # Example
+///
+/// ```
+/// use headers::{should_send_continue, ContinueDecision, Expect};
+///
+/// assert_eq!(
+///     should_send_continue(Some(&Expect::CONTINUE), true),
+///     ContinueDecision::Continue,
+/// );
+/// assert_eq!(should_send_continue(None, true), ContinueDecision::Proceed);
+/// ```
+pub fn should_send_continue(expect: Option<&Expect>, body_acceptable: bool) -> ContinueDecision {
+    match expect {
+        Some(_) if body_acceptable => ContinueDecision::Continue,
+        Some(_) => ContinueDecision::Reject,
+        None => ContinueDecision::Proceed,
+    }
+}
+
 impl fmt::Debug for Expect {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_tuple("Expect").field(&"100-continue").finish()
@@ -61,7 +109,7 @@ impl fmt::Debug for Expect {
 #[cfg(test)]
 mod tests {
     use super::super::test_decode;
-    use super::Expect;
+    use super::{should_send_continue, ContinueDecision, Expect};
 
     #[test]
     fn expect_continue() {
@@ -83,4 +131,18 @@ mod tests {
             None,
         );
     }
+
+    #[test]
+    fn continue_decision() {
+        assert_eq!(
+            should_send_continue(Some(&Expect::CONTINUE), true),
+            ContinueDecision::Continue,
+        );
+        assert_eq!(
+            should_send_continue(Some(&Expect::CONTINUE), false),
+            ContinueDecision::Reject,
+        );
+        assert_eq!(should_send_continue(None, true), ContinueDecision::Proceed);
+        assert_eq!(should_send_continue(None, false), ContinueDecision::Proceed);
+    }
 }
@@ -48,3 +48,5 @@ impl From<Expires> for SystemTime {
         date.0.into()
     }
 }
+
+date_conversions!(Expires);
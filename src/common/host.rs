@@ -20,6 +20,39 @@ impl Host {
     pub fn port(&self) -> Option<u16> {
         self.0.port_u16()
     }
+
+    /// Check if both hosts refer to the same authority, for a request with
+    /// the given `scheme`.
+    ///
+    /// Hostnames are compared case-insensitively, and a missing port is
+    /// the same as the default port of the scheme (80 for `http` and `ws`,
+    /// 443 for `https` and `wss`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::Host;
+    /// use http::uri::Authority;
+    ///
+    /// let a = Host::from(Authority::from_static("example.com"));
+    /// let b = Host::from(Authority::from_static("Example.com:80"));
+    ///
+    /// assert!(a.matches(&b, "http"));
+    /// assert!(!a.matches(&b, "https"));
+    /// ```
+    pub fn matches(&self, other: &Host, scheme: &str) -> bool {
+        let default_port =
+            if scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("ws") {
+                Some(80)
+            } else if scheme.eq_ignore_ascii_case("https") || scheme.eq_ignore_ascii_case("wss") {
+                Some(443)
+            } else {
+                None
+            };
+
+        self.hostname().eq_ignore_ascii_case(other.hostname())
+            && self.port().or(default_port) == other.port().or(default_port)
+    }
 }
 
 impl Header for Host {
@@ -55,3 +88,31 @@ impl fmt::Display for Host {
         fmt::Display::fmt(&self.0, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_decode;
+    use super::*;
+
+    fn host(s: &str) -> Host {
+        test_decode::<Host>(&[s]).unwrap()
+    }
+
+    #[test]
+    fn matches_case_insensitive() {
+        assert!(host("example.com").matches(&host("EXAMPLE.Com"), "http"));
+        assert!(host("example.com:8080").matches(&host("Example.com:8080"), "https"));
+        assert!(!host("example.com").matches(&host("example.org"), "http"));
+    }
+
+    #[test]
+    fn matches_default_port() {
+        assert!(host("example.com").matches(&host("Example.com:80"), "http"));
+        assert!(host("example.com:443").matches(&host("example.com"), "HTTPS"));
+        assert!(host("example.com:443").matches(&host("example.com"), "wss"));
+        assert!(!host("example.com").matches(&host("example.com:80"), "https"));
+        assert!(!host("example.com").matches(&host("example.com:8080"), "http"));
+        assert!(!host("example.com").matches(&host("example.com:80"), "ftp"));
+        assert!(host("example.com:21").matches(&host("example.com:21"), "ftp"));
+    }
+}
@@ -1,7 +1,7 @@
 use http::HeaderValue;
 
 use super::ETag;
-use crate::util::EntityTagRange;
+use crate::util::{EntityTagRange, FlatCsv};
 
 /// `If-None-Match` header, defined in
 /// [RFC7232](https://tools.ietf.org/html/rfc7232#section-3.2)
@@ -52,6 +52,41 @@ impl IfNoneMatch {
         IfNoneMatch(EntityTagRange::Any)
     }
 
+    /// Create a new `If-None-Match` header listing `tags`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{ETag, IfNoneMatch};
+    ///
+    /// let tags = vec!["\"a\"".parse::<ETag>().unwrap(), "W/\"b\"".parse().unwrap()];
+    /// let if_none_match = IfNoneMatch::from_tags(tags);
+    /// assert_eq!(if_none_match.iter().count(), 2);
+    /// ```
+    pub fn from_tags<I>(tags: I) -> IfNoneMatch
+    where
+        I: IntoIterator<Item = ETag>,
+    {
+        let flat = tags
+            .into_iter()
+            .map(|etag| HeaderValue::from(etag.0))
+            .collect::<FlatCsv>();
+        IfNoneMatch(EntityTagRange::Tags(flat))
+    }
+
+    /// Iterate the listed entity-tags.
+    ///
+    /// Nothing is yielded for `If-None-Match: *`.
+    pub fn iter(&self) -> impl Iterator<Item = ETag> + '_ {
+        let tags = match self.0 {
+            EntityTagRange::Any => None,
+            EntityTagRange::Tags(ref tags) => Some(tags),
+        };
+        tags.into_iter()
+            .flat_map(FlatCsv::iter)
+            .filter_map(|s| s.parse().ok())
+    }
+
     /// Checks whether the ETag passes this precondition.
     pub fn precondition_passes(&self, etag: &ETag) -> bool {
         !self.0.matches_weak(&etag.0)
@@ -76,6 +111,7 @@ test_if_none_match {
 
 #[cfg(test)]
 mod tests {
+    use super::super::test_encode;
     use super::*;
 
     #[test]
@@ -107,5 +143,22 @@ mod tests {
         let if_none = IfNoneMatch::any();
 
         assert!(!if_none.precondition_passes(&foo));
+        assert_eq!(if_none.iter().count(), 0);
+    }
+
+    #[test]
+    fn from_tags() {
+        let foo = ETag::from_static("\"foo\"");
+        let weak_bar = ETag::from_static("W/\"bar\"");
+
+        let if_none = IfNoneMatch::from_tags(vec![foo.clone(), weak_bar.clone()]);
+        assert_eq!(if_none.iter().collect::<Vec<_>>(), [foo.clone(), weak_bar]);
+
+        assert!(!if_none.precondition_passes(&foo));
+        assert!(!if_none.precondition_passes(&ETag::from_static("\"bar\"")));
+        assert!(if_none.precondition_passes(&ETag::from_static("\"baz\"")));
+
+        let headers = test_encode(if_none);
+        assert_eq!(headers["if-none-match"], "\"foo\", W/\"bar\"");
     }
 }
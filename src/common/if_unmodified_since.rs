@@ -38,7 +38,11 @@ derive_header! {
 }
 
 impl IfUnmodifiedSince {
-    /// Check if the supplied time passes the precondtion.
+    /// Check if the supplied time passes the precondition.
+    ///
+    /// The precondition passes if the resource hasn't been modified since
+    /// the date in this header. HTTP dates only have second precision, so
+    /// `last_modified` is truncated to the second before comparing.
     pub fn precondition_passes(&self, last_modified: SystemTime) -> bool {
         self.0 >= last_modified.into()
     }
@@ -56,6 +60,8 @@ impl From<IfUnmodifiedSince> for SystemTime {
     }
 }
 
+date_conversions!(IfUnmodifiedSince);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,4 +78,17 @@ mod tests {
         assert!(if_unmod.precondition_passes(exact));
         assert!(if_unmod.precondition_passes(older));
     }
+
+    #[test]
+    fn precondition_passes_second_granularity() {
+        let date = SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_777);
+        let if_unmod = IfUnmodifiedSince::from(date);
+
+        // Anywhere within the same second still passes.
+        assert!(if_unmod.precondition_passes(date));
+        assert!(if_unmod.precondition_passes(date + Duration::from_millis(999)));
+        assert!(if_unmod.precondition_passes(date - Duration::from_millis(1)));
+
+        assert!(!if_unmod.precondition_passes(date + Duration::from_secs(1)));
+    }
 }
@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use http::{HeaderName, HeaderValue};
+
+use crate::util::{params, IterExt};
+use crate::{Error, Header};
+
+static KEEP_ALIVE: HeaderName = HeaderName::from_static("keep-alive");
+
+/// `Keep-Alive` header, defined in
+/// [RFC2068](https://datatracker.ietf.org/doc/html/rfc2068#section-19.7.1.1)
+///
+/// The `Keep-Alive` header field is sent along with `Connection:
+/// keep-alive`, to tell how long an idle connection is kept open, and how
+/// many more requests it may carry.
+///
+/// # ABNF
+///
+/// ```text
+/// Keep-Alive = #( "timeout" "=" delta-seconds / "max" "=" 1*DIGIT / token [ "=" value ] )
+/// ```
+///
+/// # Example values
+/// * `timeout=5, max=1000`
+/// * `timeout=5`
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use headers::{HeaderMap, HeaderMapExt, KeepAlive};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.typed_insert(KeepAlive::new(Duration::from_secs(5), Some(1000)));
+///
+/// assert_eq!(headers["keep-alive"], "timeout=5, max=1000");
+/// let keep_alive = headers.typed_get::<KeepAlive>().unwrap();
+/// assert_eq!(keep_alive.timeout(), Some(Duration::from_secs(5)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeepAlive {
+    timeout: Option<u64>,
+    max: Option<u64>,
+}
+
+impl KeepAlive {
+    /// Create a `Keep-Alive` header with a `timeout`, and optionally a `max`
+    /// number of requests.
+    ///
+    /// The timeout is truncated to seconds.
+    pub fn new(timeout: Duration, max: Option<u64>) -> KeepAlive {
+        KeepAlive {
+            timeout: Some(timeout.as_secs()),
+            max,
+        }
+    }
+
+    /// How long an idle connection is kept open, if sent.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+
+    /// How many more requests the connection may carry, if sent.
+    pub fn max(&self) -> Option<u64> {
+        self.max
+    }
+}
+
+impl Header for KeepAlive {
+    fn name() -> &'static HeaderName {
+        &KEEP_ALIVE
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, Error> {
+        let value = values.just_one().ok_or_else(Error::invalid)?;
+        let params = params::parse(value.to_str().map_err(|_| Error::invalid())?);
+
+        // Unknown parameters are ignored, but known ones have to be valid.
+        let number = |key: &str| match params.get(key) {
+            None => Ok(None),
+            Some(Some(value)) => value.parse().map(Some).map_err(|_| Error::invalid()),
+            Some(None) => Err(Error::invalid()),
+        };
+        Ok(KeepAlive {
+            timeout: number("timeout")?,
+            max: number("max")?,
+        })
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let params = [("timeout", self.timeout), ("max", self.max)]
+            .iter()
+            .filter_map(|&(key, value)| Some(format!("{}={}", key, value?)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let value = HeaderValue::from_str(&params).expect("numbers are valid HeaderValues");
+        values.extend(::std::iter::once(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let keep_alive = test_decode::<KeepAlive>(&["timeout=5, max=1000"]).unwrap();
+        assert_eq!(keep_alive.timeout(), Some(Duration::from_secs(5)));
+        assert_eq!(keep_alive.max(), Some(1000));
+
+        let keep_alive = test_decode::<KeepAlive>(&["Timeout=\"10\"; foo"]).unwrap();
+        assert_eq!(keep_alive.timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(keep_alive.max(), None);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<KeepAlive>(&["timeout=soon"]), None);
+        assert_eq!(test_decode::<KeepAlive>(&["timeout"]), None);
+        assert_eq!(test_decode::<KeepAlive>(&["max=-1"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(KeepAlive::new(Duration::from_millis(5500), None));
+        assert_eq!(headers["keep-alive"], "timeout=5");
+    }
+}
@@ -48,3 +48,45 @@ impl From<LastModified> for SystemTime {
         date.0.into()
     }
 }
+
+date_conversions!(LastModified);
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    use super::super::{test_decode, test_encode};
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    use super::LastModified;
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_roundtrip() {
+        use chrono::TimeZone;
+
+        let dt = chrono::Utc
+            .with_ymd_and_hms(1994, 10, 29, 19, 43, 31)
+            .unwrap();
+        let last_modified = LastModified::from(dt);
+
+        let headers = test_encode(last_modified);
+        assert_eq!(headers["last-modified"], "Sat, 29 Oct 1994 19:43:31 GMT");
+
+        let decoded = test_decode::<LastModified>(&["Sat, 29 Oct 1994 19:43:31 GMT"]).unwrap();
+        assert_eq!(decoded, last_modified);
+        assert_eq!(chrono::DateTime::<chrono::Utc>::from(decoded), dt);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_roundtrip() {
+        let dt = time::OffsetDateTime::from_unix_timestamp(783_459_811).unwrap();
+        let last_modified = LastModified::from(dt);
+
+        let headers = test_encode(last_modified);
+        assert_eq!(headers["last-modified"], "Sat, 29 Oct 1994 19:43:31 GMT");
+
+        let decoded = test_decode::<LastModified>(&["Sat, 29 Oct 1994 19:43:31 GMT"]).unwrap();
+        assert_eq!(decoded, last_modified);
+        assert_eq!(time::OffsetDateTime::from(decoded), dt);
+    }
+}
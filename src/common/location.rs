@@ -1,3 +1,7 @@
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use http::uri::{InvalidUri, Uri};
 use http::HeaderValue;
 
 /// `Location` header, defined in
@@ -8,6 +12,14 @@ use http::HeaderValue;
 /// relationship is defined by the combination of request method and
 /// status code semantics.
 ///
+/// Since the value is a URI-reference, it may be relative to the effective
+/// request URI. The raw value is kept as-is, and only parsed into a `Uri`
+/// when asked via [`Location::to_uri`].
+///
+/// Some servers send redirect targets with raw UTF-8 instead of
+/// percent-encoding them. Those still decode and parse with `to_uri`, but
+/// aren't visible ASCII, so [`Location::as_str`] returns `None` for them.
+///
 /// # ABNF
 ///
 /// ```text
@@ -20,7 +32,13 @@ use http::HeaderValue;
 ///
 /// # Examples
 ///
-#[derive(Clone, Debug, PartialEq)]
+/// ```
+/// use headers::Location;
+///
+/// let loc = Location::from_static("/People.html#tim");
+/// assert_eq!(loc.as_str(), Some("/People.html#tim"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Location(HeaderValue);
 
 derive_header! {
@@ -28,17 +46,61 @@ derive_header! {
     name: LOCATION
 }
 
+impl Location {
+    /// Create a `Location` with a static string.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the string is not a legal header value.
+    pub const fn from_static(s: &'static str) -> Location {
+        Location(HeaderValue::from_static(s))
+    }
+
+    /// View this `Location` as a `&str`.
+    ///
+    /// Returns `None` if the value contains anything but visible ASCII.
+    pub fn as_str(&self) -> Option<&str> {
+        self.0.to_str().ok()
+    }
+
+    /// Try to parse this `Location` into a `Uri`.
+    ///
+    /// Relative references are supported as far as `Uri` can represent them,
+    /// such as absolute paths (`/foo`). Any fragment is dropped.
+    pub fn to_uri(&self) -> Result<Uri, InvalidUri> {
+        Uri::try_from(self.0.as_bytes())
+    }
+}
+
+error_type!(InvalidLocation);
+
+impl FromStr for Location {
+    type Err = InvalidLocation;
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        HeaderValue::from_str(src)
+            .map(Location)
+            .map_err(|_| InvalidLocation { _inner: () })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::super::test_decode;
+    use super::super::{test_decode, test_encode};
     use super::*;
+    use crate::Header;
 
     #[test]
     fn absolute_uri() {
         let s = "http://www.example.net/index.html";
         let loc = test_decode::<Location>(&[s]).unwrap();
 
-        assert_eq!(loc, Location(HeaderValue::from_static(s)));
+        assert_eq!(loc, Location::from_static(s));
+        assert_eq!(loc.as_str(), Some(s));
+
+        let uri = loc.to_uri().unwrap();
+        assert_eq!(uri.scheme_str(), Some("http"));
+        assert_eq!(uri.host(), Some("www.example.net"));
+        assert_eq!(uri.path(), "/index.html");
     }
 
     #[test]
@@ -46,6 +108,31 @@ mod tests {
         let s = "/People.html#tim";
         let loc = test_decode::<Location>(&[s]).unwrap();
 
-        assert_eq!(loc, Location(HeaderValue::from_static(s)));
+        assert_eq!(loc, Location::from_static(s));
+        assert_eq!(loc.as_str(), Some(s));
+
+        let uri = loc.to_uri().unwrap();
+        assert_eq!(uri.scheme_str(), None);
+        assert_eq!(uri.path(), "/People.html");
+    }
+
+    #[test]
+    fn obs_text() {
+        let value = HeaderValue::from_bytes(b"/caf\xc3\xa9").unwrap();
+        let loc = Location::decode(&mut std::iter::once(&value)).unwrap();
+
+        assert_eq!(loc.as_str(), None);
+        assert_eq!(loc.to_uri().unwrap().path(), "/caf\u{e9}");
+
+        let headers = test_encode(loc);
+        assert_eq!(headers["location"], value);
+    }
+
+    #[test]
+    fn encode_keeps_raw_value() {
+        let s = "/People.html#tim";
+        let headers = test_encode(s.parse::<Location>().unwrap());
+
+        assert_eq!(headers["location"], s);
     }
 }
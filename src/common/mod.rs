@@ -7,7 +7,7 @@
 //! is used, such as `ContentType(pub Mime)`.
 
 //pub use self::accept_charset::AcceptCharset;
-//pub use self::accept_encoding::AcceptEncoding;
+pub use self::accept_encoding::{AcceptEncoding, NegotiationOutcome};
 //pub use self::accept_language::AcceptLanguage;
 pub use self::accept_ranges::AcceptRanges;
 //pub use self::accept::Accept;
@@ -22,8 +22,9 @@ pub use self::access_control_request_method::AccessControlRequestMethod;
 pub use self::age::Age;
 pub use self::allow::Allow;
 pub use self::authorization::Authorization;
-pub use self::cache_control::CacheControl;
+pub use self::cache_control::{freshness_lifetime, CacheControl};
 pub use self::connection::Connection;
+pub use self::content_digest::ContentDigest;
 pub use self::content_disposition::ContentDisposition;
 pub use self::content_encoding::ContentEncoding;
 //pub use self::content_language::ContentLanguage;
@@ -34,7 +35,7 @@ pub use self::content_type::ContentType;
 pub use self::cookie::Cookie;
 pub use self::date::Date;
 pub use self::etag::ETag;
-pub use self::expect::Expect;
+pub use self::expect::{should_send_continue, ContinueDecision, Expect};
 pub use self::expires::Expires;
 //pub use self::from::From;
 pub use self::host::Host;
@@ -43,6 +44,7 @@ pub use self::if_modified_since::IfModifiedSince;
 pub use self::if_none_match::IfNoneMatch;
 pub use self::if_range::IfRange;
 pub use self::if_unmodified_since::IfUnmodifiedSince;
+pub use self::keep_alive::KeepAlive;
 //pub use self::last_event_id::LastEventId;
 pub use self::last_modified::LastModified;
 //pub use self::link::{Link, LinkValue, RelationType, MediaDesc};
@@ -57,17 +59,21 @@ pub use self::referer::Referer;
 pub use self::referrer_policy::ReferrerPolicy;
 pub use self::retry_after::RetryAfter;
 pub use self::sec_websocket_accept::SecWebsocketAccept;
+pub use self::sec_websocket_extensions::{SecWebsocketExtensions, WebsocketExtension};
 pub use self::sec_websocket_key::SecWebsocketKey;
+pub use self::sec_websocket_protocol::SecWebsocketProtocol;
 pub use self::sec_websocket_version::SecWebsocketVersion;
 pub use self::server::Server;
 pub use self::set_cookie::SetCookie;
 pub use self::strict_transport_security::StrictTransportSecurity;
 pub use self::te::Te;
-pub use self::transfer_encoding::TransferEncoding;
+pub use self::transfer_encoding::{validate_framing, TransferEncoding};
 pub use self::upgrade::Upgrade;
 pub use self::user_agent::UserAgent;
 pub use self::vary::Vary;
-//pub use self::warning::Warning;
+pub use self::warning::Warning;
+pub use self::www_authenticate::WwwAuthenticate;
+pub use self::x_forwarded_for::XForwardedFor;
 
 #[cfg(test)]
 fn test_decode<T: crate::Header>(values: &[&str]) -> Option<T> {
@@ -128,7 +134,7 @@ macro_rules! bench_header {
 
 //mod accept;
 //mod accept_charset;
-//mod accept_encoding;
+mod accept_encoding;
 //mod accept_language;
 mod accept_ranges;
 mod access_control_allow_credentials;
@@ -144,6 +150,7 @@ mod allow;
 pub mod authorization;
 mod cache_control;
 mod connection;
+mod content_digest;
 mod content_disposition;
 mod content_encoding;
 //mod content_language;
@@ -163,6 +170,7 @@ mod if_modified_since;
 mod if_none_match;
 mod if_range;
 mod if_unmodified_since;
+mod keep_alive;
 //mod last_event_id;
 mod last_modified;
 //mod link;
@@ -177,7 +185,9 @@ mod referer;
 mod referrer_policy;
 mod retry_after;
 mod sec_websocket_accept;
+mod sec_websocket_extensions;
 mod sec_websocket_key;
+mod sec_websocket_protocol;
 mod sec_websocket_version;
 mod server;
 mod set_cookie;
@@ -187,4 +197,6 @@ mod transfer_encoding;
 mod upgrade;
 mod user_agent;
 mod vary;
-//mod warning;
+mod warning;
+mod www_authenticate;
+mod x_forwarded_for;
@@ -1,6 +1,6 @@
 use http::{HeaderName, HeaderValue};
 
-use super::authorization::{Authorization, Credentials};
+use super::authorization::{self, Authorization, Basic, Bearer, Credentials, InvalidBearerToken};
 use crate::{Error, Header};
 
 /// `Proxy-Authorization` header, defined in [RFC7235](https://tools.ietf.org/html/rfc7235#section-4.4)
@@ -27,24 +27,91 @@ use crate::{Error, Header};
 #[derive(Clone, PartialEq, Debug)]
 pub struct ProxyAuthorization<C: Credentials>(pub C);
 
+impl ProxyAuthorization<Basic> {
+    /// Create a `Basic` proxy authorization header.
+    pub fn basic(username: &str, password: &str) -> Self {
+        ProxyAuthorization(Authorization::basic(username, password).0)
+    }
+
+    /// View the decoded username.
+    pub fn username(&self) -> &str {
+        self.0.username()
+    }
+
+    /// View the decoded password.
+    pub fn password(&self) -> &str {
+        self.0.password()
+    }
+}
+
+impl ProxyAuthorization<Bearer> {
+    /// Try to create a `Bearer` proxy authorization header.
+    ///
+    /// The token must be non-empty, and may not contain spaces or
+    /// control characters.
+    pub fn bearer(token: &str) -> Result<Self, InvalidBearerToken> {
+        Authorization::bearer(token).map(|auth| ProxyAuthorization(auth.0))
+    }
+
+    /// View the token part as a `&str`.
+    pub fn token(&self) -> &str {
+        self.0.token()
+    }
+}
+
 impl<C: Credentials> Header for ProxyAuthorization<C> {
     fn name() -> &'static HeaderName {
         &::http::header::PROXY_AUTHORIZATION
     }
 
     fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, Error> {
-        Authorization::decode(values).map(|auth| ProxyAuthorization(auth.0))
+        authorization::decode_credentials(values).map(ProxyAuthorization)
     }
 
     fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
-        let value = self.0.encode();
-        debug_assert!(
-            value.as_bytes().starts_with(C::SCHEME.as_bytes()),
-            "Credentials::encode should include its scheme: scheme = {:?}, encoded = {:?}",
-            C::SCHEME,
-            value,
+        values.extend(::std::iter::once(authorization::encode_credentials(
+            &self.0,
+        )));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn basic_decode() {
+        let auth =
+            test_decode::<ProxyAuthorization<Basic>>(&["Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="])
+                .unwrap();
+        assert_eq!(auth.username(), "Aladdin");
+        assert_eq!(auth.password(), "open sesame");
+        assert_eq!(auth, ProxyAuthorization::basic("Aladdin", "open sesame"));
+    }
+
+    #[test]
+    fn basic_encode_is_sensitive() {
+        let headers = test_encode(ProxyAuthorization::basic("Aladdin", "open sesame"));
+        let value = &headers["proxy-authorization"];
+
+        assert_eq!(value, "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==");
+        assert!(value.is_sensitive());
+    }
+
+    #[test]
+    fn bearer() {
+        let auth =
+            test_decode::<ProxyAuthorization<Bearer>>(&["Bearer fpKL54jvWmEGVoRdCNjG"]).unwrap();
+        assert_eq!(auth.token(), "fpKL54jvWmEGVoRdCNjG");
+        assert_eq!(
+            auth,
+            ProxyAuthorization::bearer("fpKL54jvWmEGVoRdCNjG").unwrap()
         );
 
-        values.extend(::std::iter::once(value));
+        assert_eq!(
+            test_decode::<ProxyAuthorization<Bearer>>(&["Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="]),
+            None
+        );
     }
 }
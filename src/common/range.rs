@@ -65,6 +65,28 @@ impl Range {
         Ok(Range(HeaderValue::from_str(&v).unwrap()))
     }
 
+    /// Creates a `Range: bytes=-n` header, requesting the last `n` bytes.
+    ///
+    /// If the representation is shorter than `n` bytes,
+    /// [`satisfiable_ranges`](Range::satisfiable_ranges) resolves it to the
+    /// whole representation. A suffix of 0 bytes can't be satisfied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use headers::Range;
+    ///
+    /// let range = Range::suffix(100);
+    /// assert_eq!(
+    ///     range.satisfiable_ranges(350).collect::<Vec<_>>(),
+    ///     vec![(Bound::Included(250), Bound::Unbounded)],
+    /// );
+    /// ```
+    pub fn suffix(n: u64) -> Self {
+        Range(HeaderValue::from_str(&format!("bytes=-{}", n)).unwrap())
+    }
+
     /// Iterate the range sets as a tuple of bounds, if valid with length.
     ///
     /// The length of the content is passed as an argument, and all ranges
@@ -87,11 +109,12 @@ impl Range {
             // For example, `-100` means the last 100 bytes.
             if let Bound::Unbounded = start {
                 if let Bound::Included(end) = end {
-                    if len < end {
-                        // Last N bytes is larger than available!
+                    if end == 0 || len == 0 {
                         return None;
                     }
-                    return Some((Bound::Included(len - end), Bound::Unbounded));
+                    // If the last N bytes are more than available, the
+                    // whole representation is used.
+                    return Some((Bound::Included(len.saturating_sub(end)), Bound::Unbounded));
                 }
                 // else fall through
             }
@@ -451,8 +474,35 @@ fn test_to_satisfiable_range_suffix() {
 }
 
 #[test]
-fn test_to_unsatisfiable_range_suffix() {
+fn test_to_satisfiable_range_suffix_larger_than_len() {
     let range = super::test_decode::<Range>(&["bytes=-350"]).unwrap();
-    let bounds = range.satisfiable_ranges(100).next();
-    assert_eq!(bounds, None);
+    let bounds = range.satisfiable_ranges(100).next().unwrap();
+    assert_eq!(bounds, (Bound::Included(0), Bound::Unbounded));
+}
+
+#[test]
+fn test_to_unsatisfiable_range_suffix() {
+    let range = super::test_decode::<Range>(&["bytes=-0"]).unwrap();
+    assert_eq!(range.satisfiable_ranges(100).next(), None);
+
+    let range = super::test_decode::<Range>(&["bytes=-100"]).unwrap();
+    assert_eq!(range.satisfiable_ranges(0).next(), None);
+}
+
+#[test]
+fn test_suffix() {
+    let range = Range::suffix(100);
+    assert_eq!(range, super::test_decode::<Range>(&["bytes=-100"]).unwrap());
+
+    let headers = super::test_encode(range.clone());
+    assert_eq!(headers["range"], "bytes=-100");
+
+    assert_eq!(
+        range.satisfiable_ranges(350).collect::<Vec<_>>(),
+        vec![(Bound::Included(250), Bound::Unbounded)]
+    );
+    assert_eq!(
+        range.satisfiable_ranges(50).collect::<Vec<_>>(),
+        vec![(Bound::Included(0), Bound::Unbounded)]
+    );
 }
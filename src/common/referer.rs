@@ -1,7 +1,11 @@
 use std::fmt;
 use std::str::FromStr;
 
-use crate::util::HeaderValueString;
+use http::header::{HeaderName, HeaderValue};
+use http::Uri;
+
+use crate::util::{HeaderValueString, IterExt};
+use crate::{Error, Header, Origin};
 
 /// `Referer` header, defined in
 /// [RFC7231](https://datatracker.ietf.org/doc/html/rfc7231#section-5.5.2)
@@ -27,14 +31,31 @@ use crate::util::HeaderValueString;
 /// ```
 /// use headers::Referer;
 ///
-/// let r = Referer::from_static("/People.html#tim");
+/// let r = Referer::from_static("/People.html");
+///
+/// // A fragment is stripped when parsing or decoding.
+/// let r = "http://www.example.org/People.html#tim".parse::<Referer>().unwrap();
+/// assert_eq!(r.as_str(), "http://www.example.org/People.html");
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct Referer(HeaderValueString);
 
-derive_header! {
-    Referer(_),
-    name: REFERER
+impl Header for Referer {
+    fn name() -> &'static HeaderName {
+        &::http::header::REFERER
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, Error> {
+        values
+            .just_one()
+            .and_then(|value| value.to_str().ok())
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
 }
 
 impl Referer {
@@ -46,6 +67,26 @@ impl Referer {
     pub const fn from_static(s: &'static str) -> Referer {
         Referer(HeaderValueString::from_static(s))
     }
+
+    /// View this `Referer` as a `&str`.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Parse this `Referer` as a `Uri`.
+    ///
+    /// A fragment, which a user agent shouldn't have sent, is dropped.
+    pub fn to_uri(&self) -> Option<Uri> {
+        self.as_str().parse().ok()
+    }
+
+    /// Get the origin (scheme, host and port) of the referring resource.
+    ///
+    /// Returns `None` if this is a partial URI, like `/People.html`.
+    pub fn origin(&self) -> Option<Origin> {
+        let uri = self.to_uri()?;
+        Origin::try_from_parts(uri.scheme_str()?, uri.host()?, uri.port_u16()).ok()
+    }
 }
 
 error_type!(InvalidReferer);
@@ -53,6 +94,11 @@ error_type!(InvalidReferer);
 impl FromStr for Referer {
     type Err = InvalidReferer;
     fn from_str(src: &str) -> Result<Self, Self::Err> {
+        // `Header::decode` parses through here too, so both drop the fragment.
+        let src = match src.find('#') {
+            Some(idx) => &src[..idx],
+            None => src,
+        };
         HeaderValueString::from_str(src)
             .map(Referer)
             .map_err(|_| InvalidReferer { _inner: () })
@@ -64,3 +110,47 @@ impl fmt::Display for Referer {
         fmt::Display::fmt(&self.0, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_decode;
+    use super::*;
+
+    #[test]
+    fn full_url() {
+        let referer =
+            test_decode::<Referer>(&["https://www.example.org:8443/hypertext/Overview.html?q=1"])
+                .unwrap();
+
+        let uri = referer.to_uri().unwrap();
+        assert_eq!(uri.path(), "/hypertext/Overview.html");
+        assert_eq!(uri.query(), Some("q=1"));
+
+        let origin = referer.origin().unwrap();
+        assert_eq!(origin.scheme(), "https");
+        assert_eq!(origin.hostname(), "www.example.org");
+        assert_eq!(origin.port(), Some(8443));
+    }
+
+    #[test]
+    fn partial_uri() {
+        let referer = test_decode::<Referer>(&["/People.html"]).unwrap();
+        assert_eq!(referer.to_uri().unwrap().path(), "/People.html");
+        assert_eq!(referer.origin(), None);
+    }
+
+    #[test]
+    fn fragment() {
+        let referer = "http://www.example.org/People.html#tim"
+            .parse::<Referer>()
+            .unwrap();
+        assert_eq!(referer.as_str(), "http://www.example.org/People.html");
+
+        let referer = test_decode::<Referer>(&["http://www.example.org/People.html#tim"]).unwrap();
+        assert_eq!(referer.as_str(), "http://www.example.org/People.html");
+        assert_eq!(
+            referer.to_uri().unwrap(),
+            "http://www.example.org/People.html"
+        );
+    }
+}
@@ -47,7 +47,28 @@ impl RetryAfter {
         RetryAfter(After::DateTime(time.into()))
     }
 
-    /// Create an `RetryAfter` header with a date value.
+    /// Create an `RetryAfter` header with a date value, from any type that
+    /// converts into a `SystemTime`.
+    ///
+    /// This needs none of this crate's features: the bound is std's `Into`,
+    /// so `chrono::DateTime` and `time::OffsetDateTime` work as long as their
+    /// crates' own `std` feature is on. The `chrono` and `time` features only
+    /// add `From` impls on the header itself. The date is truncated to seconds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use headers::RetryAfter;
+    ///
+    /// let at = SystemTime::now() + Duration::from_secs(60);
+    /// assert_eq!(RetryAfter::from_datetime(at), RetryAfter::date(at));
+    /// ```
+    pub fn from_datetime<T: Into<SystemTime>>(datetime: T) -> RetryAfter {
+        RetryAfter::date(datetime.into())
+    }
+
+    /// Create an `RetryAfter` header with a delay value.
     pub fn delay(dur: Duration) -> RetryAfter {
         RetryAfter(After::Delay(dur.into()))
     }
@@ -110,4 +131,24 @@ mod tests {
     test_retry_after_datetime!(date_decode_rfc1123, "Sun, 06 Nov 1994 08:49:37 GMT");
     test_retry_after_datetime!(date_decode_rfc850, "Sunday, 06-Nov-94 08:49:37 GMT");
     test_retry_after_datetime!(date_decode_asctime, "Sun Nov  6 08:49:37 1994");
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn from_chrono() {
+        use chrono::TimeZone;
+
+        let dt = chrono::Utc
+            .with_ymd_and_hms(1994, 11, 6, 8, 49, 37)
+            .unwrap();
+        let r: RetryAfter = test_decode(&["Sun, 06 Nov 1994 08:49:37 GMT"]).unwrap();
+        assert_eq!(RetryAfter::from_datetime(dt), r);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn from_time() {
+        let dt = time::OffsetDateTime::from_unix_timestamp(784_111_777).unwrap();
+        let r: RetryAfter = test_decode(&["Sun, 06 Nov 1994 08:49:37 GMT"]).unwrap();
+        assert_eq!(RetryAfter::from_datetime(dt), r);
+    }
 }
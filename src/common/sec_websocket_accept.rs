@@ -30,6 +30,33 @@ derive_header! {
     name: SEC_WEBSOCKET_ACCEPT
 }
 
+impl SecWebsocketAccept {
+    /// Compute the `Sec-WebSocket-Accept` a server answers the given key
+    /// with, as defined in
+    /// [RFC6455](https://tools.ietf.org/html/rfc6455#section-4.2.2).
+    ///
+    /// This is the same as `SecWebsocketAccept::from(key.clone())`.
+    pub fn from_key(key: &SecWebsocketKey) -> SecWebsocketAccept {
+        sign(key.0.as_bytes())
+    }
+
+    /// Check if this is the accept value for the given key, as a client
+    /// has to before completing the handshake.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{SecWebsocketAccept, SecWebsocketKey};
+    ///
+    /// let key = SecWebsocketKey::from([0; 16]);
+    /// let accept = SecWebsocketAccept::from_key(&key);
+    /// assert!(accept.verify(&key));
+    /// ```
+    pub fn verify(&self, key: &SecWebsocketKey) -> bool {
+        *self == SecWebsocketAccept::from_key(key)
+    }
+}
+
 impl From<SecWebsocketKey> for SecWebsocketAccept {
     fn from(key: SecWebsocketKey) -> SecWebsocketAccept {
         sign(key.0.as_bytes())
@@ -64,4 +91,18 @@ mod tests {
             "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
         );
     }
+
+    #[test]
+    fn from_key_and_verify() {
+        // From https://tools.ietf.org/html/rfc6455#section-1.3
+        let key = test_decode::<SecWebsocketKey>(&["dGhlIHNhbXBsZSBub25jZQ=="]).expect("key");
+        let accept =
+            test_decode::<SecWebsocketAccept>(&["s3pPLMBiTxaQ9kYGzzhZRbK+xOo="]).expect("accept");
+
+        assert_eq!(SecWebsocketAccept::from_key(&key), accept);
+        assert!(accept.verify(&key));
+
+        let other = test_decode::<SecWebsocketKey>(&["AQIDBAUGBwgJCgsMDQ4PEA=="]).expect("key");
+        assert!(!accept.verify(&other));
+    }
 }
@@ -0,0 +1,219 @@
+use std::convert::TryFrom;
+
+use http::HeaderValue;
+
+use crate::util::FlatCsv;
+use crate::Error;
+
+/// The `Sec-Websocket-Extensions` header, defined in
+/// [RFC6455](https://tools.ietf.org/html/rfc6455#section-9.1)
+///
+/// Lists the protocol-level extensions, with their parameters, that the
+/// client wishes to use or the server agreed to.
+///
+/// # ABNF
+///
+/// ```text
+/// Sec-WebSocket-Extensions = extension-list
+/// extension-list = 1#extension
+/// extension = extension-token *( ";" extension-param )
+/// extension-param = token [ "=" (token | quoted-string) ]
+/// ```
+///
+/// # Example values
+/// * `permessage-deflate`
+/// * `permessage-deflate; client_max_window_bits, x-custom`
+/// * `permessage-deflate; server_max_window_bits=10`
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use headers::{HeaderMap, HeaderMapExt, SecWebsocketExtensions};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert(
+///     "sec-websocket-extensions",
+///     "permessage-deflate; server_max_window_bits=10".parse().unwrap(),
+/// );
+/// let exts = headers.typed_get::<SecWebsocketExtensions>().unwrap();
+///
+/// let deflate = exts.iter().next().unwrap();
+/// assert_eq!(deflate.name(), "permessage-deflate");
+/// assert_eq!(deflate.param("server_max_window_bits"), Some(Some("10")));
+///
+/// // Answer with the extensions the server agreed to.
+/// let response = SecWebsocketExtensions::try_from("permessage-deflate").unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SecWebsocketExtensions(FlatCsv);
+
+derive_header! {
+    SecWebsocketExtensions(_),
+    name: SEC_WEBSOCKET_EXTENSIONS
+}
+
+impl SecWebsocketExtensions {
+    /// Iterate the extensions, in the order they were sent.
+    pub fn iter(&self) -> impl Iterator<Item = WebsocketExtension<'_>> {
+        self.0
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(|raw| WebsocketExtension { raw })
+    }
+}
+
+/// A single extension of a `Sec-Websocket-Extensions` header.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WebsocketExtension<'a> {
+    raw: &'a str,
+}
+
+impl<'a> WebsocketExtension<'a> {
+    /// The extension token, such as `permessage-deflate`.
+    pub fn name(&self) -> &'a str {
+        self.raw.split(';').next().unwrap_or("").trim()
+    }
+
+    /// Iterate the parameters of this extension.
+    ///
+    /// Flag-style parameters without a value yield `None` as their value.
+    /// Quoted-string values have their surrounding quotes removed.
+    pub fn params(&self) -> impl Iterator<Item = (&'a str, Option<&'a str>)> {
+        self.raw.split(';').skip(1).filter_map(|param| {
+            let param = param.trim();
+            if param.is_empty() {
+                return None;
+            }
+            Some(match param.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(unquote(value.trim()))),
+                None => (param, None),
+            })
+        })
+    }
+
+    /// Get a parameter by name, compared case-insensitively.
+    ///
+    /// Returns `None` if the parameter is missing, and `Some(None)` if it is
+    /// a flag-style parameter without a value.
+    pub fn param(&self, name: &str) -> Option<Option<&'a str>> {
+        self.params()
+            .find(|&(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+}
+
+impl TryFrom<&str> for SecWebsocketExtensions {
+    type Error = Error;
+
+    /// Create the header from an `extension-list`, such as
+    /// `permessage-deflate; server_max_window_bits=10`.
+    fn try_from(s: &str) -> Result<Self, Error> {
+        if !s.split(',').all(is_extension) {
+            return Err(Error::invalid());
+        }
+        let value = HeaderValue::from_str(s).map_err(|_| Error::invalid())?;
+        Ok(SecWebsocketExtensions(FlatCsv::from(value)))
+    }
+}
+
+/// Check an `extension`. A quoted parameter value must still be a token, so
+/// neither `,` nor `;` can appear inside of one.
+fn is_extension(s: &str) -> bool {
+    let mut parts = s.split(';').map(str::trim);
+    parts.next().map_or(false, is_token)
+        && parts.all(|param| match param.split_once('=') {
+            Some((name, value)) => is_token(name.trim()) && is_token(unquote(value.trim())),
+            None => is_token(param),
+        })
+}
+
+fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+fn unquote(val: &str) -> &str {
+    if val.len() >= 2 && val.starts_with('"') && val.ends_with('"') {
+        &val[1..val.len() - 1]
+    } else {
+        val
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn permessage_deflate() {
+        let exts = test_decode::<SecWebsocketExtensions>(&[
+            "permessage-deflate; client_max_window_bits, x-custom",
+        ])
+        .unwrap();
+
+        let exts = exts.iter().collect::<Vec<_>>();
+        assert_eq!(exts.len(), 2);
+
+        assert_eq!(exts[0].name(), "permessage-deflate");
+        assert_eq!(exts[0].param("client_max_window_bits"), Some(None));
+        assert_eq!(exts[0].param("server_max_window_bits"), None);
+
+        assert_eq!(exts[1].name(), "x-custom");
+        assert_eq!(exts[1].params().count(), 0);
+    }
+
+    #[test]
+    fn param_values() {
+        let exts = test_decode::<SecWebsocketExtensions>(&[
+            "permessage-deflate; server_max_window_bits=10; client_max_window_bits=\"12\"",
+        ])
+        .unwrap();
+        let deflate = exts.iter().next().unwrap();
+
+        assert_eq!(
+            deflate.params().collect::<Vec<_>>(),
+            vec![
+                ("server_max_window_bits", Some("10")),
+                ("client_max_window_bits", Some("12")),
+            ]
+        );
+        assert_eq!(deflate.param("Server_Max_Window_Bits"), Some(Some("10")));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let s = "permessage-deflate; client_max_window_bits, x-custom";
+        let exts = test_decode::<SecWebsocketExtensions>(&[s]).unwrap();
+        let headers = test_encode(exts);
+        assert_eq!(headers["sec-websocket-extensions"], s);
+    }
+
+    #[test]
+    fn try_from() {
+        let s = "permessage-deflate; server_max_window_bits=10; client_max_window_bits=\"12\"";
+        let exts = SecWebsocketExtensions::try_from(s).unwrap();
+        assert_eq!(
+            exts.iter().next().unwrap().param("client_max_window_bits"),
+            Some(Some("12"))
+        );
+
+        let headers = test_encode(exts);
+        assert_eq!(headers["sec-websocket-extensions"], s);
+
+        let exts = SecWebsocketExtensions::try_from("permessage-deflate, x-custom").unwrap();
+        assert_eq!(exts.iter().count(), 2);
+    }
+
+    #[test]
+    fn try_from_invalid() {
+        assert!(SecWebsocketExtensions::try_from("").is_err());
+        assert!(SecWebsocketExtensions::try_from("permessage-deflate,").is_err());
+        assert!(SecWebsocketExtensions::try_from("; server_max_window_bits").is_err());
+        assert!(SecWebsocketExtensions::try_from("permessage-deflate; a=").is_err());
+        assert!(SecWebsocketExtensions::try_from("permessage-deflate; a=\"b c\"").is_err());
+        assert!(SecWebsocketExtensions::try_from("x; a=\"").is_err());
+    }
+}
@@ -1,13 +1,53 @@
 use base64::{engine::general_purpose::STANDARD, Engine};
-use http::HeaderValue;
+use http::{HeaderName, HeaderValue};
+
+use crate::util::IterExt;
+use crate::{Error, Header};
 
 /// The `Sec-Websocket-Key` header.
+///
+/// The key is a base64-encoded random 16-byte value, and anything else
+/// fails to decode.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct SecWebsocketKey(pub(super) HeaderValue);
 
-derive_header! {
-    SecWebsocketKey(_),
-    name: SEC_WEBSOCKET_KEY
+impl SecWebsocketKey {
+    /// Generate a random key, for a client to start a handshake with.
+    ///
+    /// This requires the `getrandom` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system's random number generator fails.
+    #[cfg(feature = "getrandom")]
+    pub fn random() -> SecWebsocketKey {
+        let mut bytes = [0; 16];
+        getrandom::getrandom(&mut bytes).expect("random number generator failed");
+        SecWebsocketKey::from(bytes)
+    }
+}
+
+impl Header for SecWebsocketKey {
+    fn name() -> &'static HeaderName {
+        &::http::header::SEC_WEBSOCKET_KEY
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, Error> {
+        values
+            .just_one()
+            .filter(|value| {
+                STANDARD
+                    .decode(value.as_bytes())
+                    .map(|bytes| bytes.len() == 16)
+                    .unwrap_or(false)
+            })
+            .map(|value| SecWebsocketKey(value.clone()))
+            .ok_or_else(Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(self.0.clone()));
+    }
 }
 
 impl From<[u8; 16]> for SecWebsocketKey {
@@ -20,8 +60,43 @@ impl From<[u8; 16]> for SecWebsocketKey {
 
 #[cfg(test)]
 mod tests {
+    use super::super::test_decode;
     use super::*;
 
+    #[test]
+    fn decode() {
+        // From https://tools.ietf.org/html/rfc6455#section-1.3
+        assert!(test_decode::<SecWebsocketKey>(&["dGhlIHNhbXBsZSBub25jZQ=="]).is_some());
+    }
+
+    #[test]
+    fn decode_invalid() {
+        for s in &[
+            "",
+            "not base64!",
+            // 15 and 17 bytes
+            "AQIDBAUGBwgJCgsMDQ4P",
+            "AQIDBAUGBwgJCgsMDQ4PEBE=",
+        ] {
+            assert_eq!(test_decode::<SecWebsocketKey>(&[s]), None, "{:?}", s);
+        }
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn random() {
+        use super::super::test_encode;
+        use crate::{HeaderMapExt, SecWebsocketAccept};
+
+        let key = SecWebsocketKey::random();
+        assert_ne!(key, SecWebsocketKey::random());
+
+        let headers = test_encode(key.clone());
+        let decoded = headers.typed_get::<SecWebsocketKey>().unwrap();
+        assert_eq!(decoded, key);
+        assert!(SecWebsocketAccept::from_key(&key).verify(&decoded));
+    }
+
     #[test]
     fn from_bytes() {
         let bytes: [u8; 16] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
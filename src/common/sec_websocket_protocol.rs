@@ -0,0 +1,142 @@
+use std::convert::TryFrom;
+
+use http::HeaderValue;
+
+use crate::util::FlatCsv;
+use crate::Error;
+
+/// The `Sec-Websocket-Protocol` header, defined in
+/// [RFC6455](https://tools.ietf.org/html/rfc6455#section-11.3.4)
+///
+/// In a request, lists the subprotocols the client wishes to speak, in
+/// order of preference. In a response, holds the single subprotocol the
+/// server selected.
+///
+/// # ABNF
+///
+/// ```text
+/// Sec-WebSocket-Protocol = 1#token
+/// ```
+///
+/// # Example values
+/// * `chat`
+/// * `graphql-transport-ws, graphql-ws`
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use headers::{HeaderMap, HeaderMapExt, SecWebsocketProtocol};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("sec-websocket-protocol", "v2.chat, v1.chat".parse().unwrap());
+/// let protocols = headers.typed_get::<SecWebsocketProtocol>().unwrap();
+///
+/// let selected = protocols.select(&["v1.chat"]).unwrap();
+/// assert_eq!(selected, "v1.chat");
+///
+/// // Send the selected subprotocol back in the response.
+/// let response = SecWebsocketProtocol::try_from(selected.as_str()).unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SecWebsocketProtocol(FlatCsv);
+
+derive_header! {
+    SecWebsocketProtocol(_),
+    name: SEC_WEBSOCKET_PROTOCOL
+}
+
+impl SecWebsocketProtocol {
+    /// Iterate the subprotocols, in the order they were sent.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter(|s| !s.is_empty())
+    }
+
+    /// Select the first subprotocol offered by the client that is also
+    /// `supported` by the server.
+    ///
+    /// Subprotocol names are compared case-sensitively.
+    pub fn select(&self, supported: &[&str]) -> Option<String> {
+        self.iter()
+            .find(|protocol| supported.contains(protocol))
+            .map(ToOwned::to_owned)
+    }
+}
+
+impl TryFrom<&str> for SecWebsocketProtocol {
+    type Error = Error;
+
+    /// Create the header from a comma-separated list of subprotocols, such
+    /// as the one returned by [`SecWebsocketProtocol::select`].
+    fn try_from(s: &str) -> Result<Self, Error> {
+        if !s.split(',').map(str::trim).all(is_token) {
+            return Err(Error::invalid());
+        }
+        let value = HeaderValue::from_str(s).map_err(|_| Error::invalid())?;
+        Ok(SecWebsocketProtocol(FlatCsv::from(value)))
+    }
+}
+
+fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn iter() {
+        let protocols =
+            test_decode::<SecWebsocketProtocol>(&["graphql-transport-ws, graphql-ws", "chat"])
+                .unwrap();
+
+        assert_eq!(
+            protocols.iter().collect::<Vec<_>>(),
+            vec!["graphql-transport-ws", "graphql-ws", "chat"]
+        );
+    }
+
+    #[test]
+    fn select_overlap() {
+        let protocols =
+            test_decode::<SecWebsocketProtocol>(&["v3.chat, v2.chat, v1.chat"]).unwrap();
+
+        assert_eq!(
+            protocols.select(&["v1.chat", "v2.chat"]),
+            Some("v2.chat".to_owned())
+        );
+    }
+
+    #[test]
+    fn select_no_overlap() {
+        let protocols = test_decode::<SecWebsocketProtocol>(&["v3.chat, v2.chat"]).unwrap();
+
+        assert_eq!(protocols.select(&["mqtt"]), None);
+        assert_eq!(protocols.select(&[]), None);
+    }
+
+    #[test]
+    fn try_from_selected() {
+        let protocols = test_decode::<SecWebsocketProtocol>(&["v2.chat, v1.chat"]).unwrap();
+        let selected = protocols.select(&["v1.chat"]).unwrap();
+
+        let response = SecWebsocketProtocol::try_from(selected.as_str()).unwrap();
+        let headers = test_encode(response);
+        assert_eq!(headers["sec-websocket-protocol"], "v1.chat");
+
+        let list = SecWebsocketProtocol::try_from("v2.chat, v1.chat").unwrap();
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec!["v2.chat", "v1.chat"]);
+    }
+
+    #[test]
+    fn try_from_invalid() {
+        assert!(SecWebsocketProtocol::try_from("").is_err());
+        assert!(SecWebsocketProtocol::try_from("v1.chat,").is_err());
+        assert!(SecWebsocketProtocol::try_from("v1 chat").is_err());
+        assert!(SecWebsocketProtocol::try_from("\"v1.chat\"").is_err());
+    }
+}
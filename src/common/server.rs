@@ -1,7 +1,7 @@
 use std::fmt;
 use std::str::FromStr;
 
-use crate::util::HeaderValueString;
+use crate::util::{self, HeaderValueString};
 
 /// `Server` header, defined in [RFC7231](https://datatracker.ietf.org/doc/html/rfc7231#section-7.4.2)
 ///
@@ -51,6 +51,13 @@ impl Server {
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Iterate the products, as `(product, version)` pairs.
+    ///
+    /// Comments, such as `(Ubuntu)`, are skipped.
+    pub fn products(&self) -> impl Iterator<Item = (&str, Option<&str>)> + '_ {
+        util::products(self.as_str())
+    }
 }
 
 error_type!(InvalidServer);
@@ -58,9 +65,9 @@ error_type!(InvalidServer);
 impl FromStr for Server {
     type Err = InvalidServer;
     fn from_str(src: &str) -> Result<Self, Self::Err> {
-        HeaderValueString::from_str(src)
+        HeaderValueString::trimmed(src)
             .map(Server)
-            .map_err(|_| InvalidServer { _inner: () })
+            .ok_or(InvalidServer { _inner: () })
     }
 }
 
@@ -69,3 +76,29 @@ impl fmt::Display for Server {
         fmt::Display::fmt(&self.0, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_trims() {
+        let val = " nginx/1.25.3 ".parse::<Server>().unwrap();
+        assert_eq!(val.as_str(), "nginx/1.25.3");
+    }
+
+    #[test]
+    fn products() {
+        let val = "nginx/1.25.3 (Ubuntu)".parse::<Server>().unwrap();
+        assert_eq!(
+            val.products().collect::<Vec<_>>(),
+            [("nginx", Some("1.25.3"))]
+        );
+    }
+
+    #[test]
+    fn from_str_empty() {
+        assert!("".parse::<Server>().is_err());
+        assert!("  ".parse::<Server>().is_err());
+    }
+}
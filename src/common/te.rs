@@ -1,6 +1,7 @@
 use http::HeaderValue;
 
-use crate::util::FlatCsv;
+use crate::util::QualityList;
+use crate::Encoding;
 
 /// `TE` header, defined in
 /// [RFC7230](https://datatracker.ietf.org/doc/html/rfc7230#section-4.3)
@@ -28,7 +29,7 @@ use crate::util::FlatCsv;
 /// # Examples
 ///
 #[derive(Clone, Debug, PartialEq)]
-pub struct Te(FlatCsv);
+pub struct Te(QualityList<Encoding>);
 
 derive_header! {
     Te(_),
@@ -41,3 +42,15 @@ impl Te {
         Te(HeaderValue::from_static("trailers").into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_decode;
+    use super::Te;
+
+    #[test]
+    fn trailers() {
+        assert_eq!(test_decode::<Te>(&["trailers"]), Some(Te::trailers()));
+        assert!(test_decode::<Te>(&["trailers, deflate;q=0.5"]).is_some());
+    }
+}
@@ -1,6 +1,7 @@
 use http::HeaderValue;
 
 use crate::util::FlatCsv;
+use crate::ContentLength;
 
 /// `Transfer-Encoding` header, defined in
 /// [RFC7230](https://datatracker.ietf.org/doc/html/rfc7230#section-3.3.1)
@@ -68,10 +69,93 @@ impl TransferEncoding {
     }
 }
 
+error_type!(InvalidFraming);
+
+/// Check that the framing headers of a message are consistent, as defined
+/// in [RFC7230](https://tools.ietf.org/html/rfc7230#section-3.3).
+///
+/// This fails if:
+///
+/// * both `Transfer-Encoding` and `Content-Length` are present, which
+///   could be used to smuggle requests,
+/// * `chunked` is applied more than once,
+/// * `chunked` is applied, but isn't the final transfer coding.
+///
+/// A `Transfer-Encoding` without `chunked` is allowed, since a response can
+/// be delimited by closing the connection. A request needs `chunked` last,
+/// which [`is_chunked`](TransferEncoding::is_chunked) checks.
+///
+/// # Example
+///
+/// ```
+/// use headers::{validate_framing, ContentLength, TransferEncoding};
+///
+/// let te = TransferEncoding::chunked();
+/// assert!(validate_framing(Some(&te), None).is_ok());
+/// assert!(validate_framing(Some(&te), Some(&ContentLength(42))).is_err());
+/// ```
+pub fn validate_framing(
+    transfer_encoding: Option<&TransferEncoding>,
+    content_length: Option<&ContentLength>,
+) -> Result<(), InvalidFraming> {
+    let te = match transfer_encoding {
+        Some(te) => te,
+        None => return Ok(()),
+    };
+    if content_length.is_some() {
+        return Err(InvalidFraming { _inner: () });
+    }
+
+    let codings = te.0.iter().filter(|s| !s.is_empty()).collect::<Vec<_>>();
+    let chunked = codings
+        .iter()
+        .filter(|s| s.eq_ignore_ascii_case("chunked"))
+        .count();
+    let chunked_last = codings
+        .last()
+        .map(|s| s.eq_ignore_ascii_case("chunked"))
+        .unwrap_or(false);
+
+    if chunked > 1 || (chunked == 1 && !chunked_last) {
+        Err(InvalidFraming { _inner: () })
+    } else {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::test_decode;
-    use super::TransferEncoding;
+    use super::*;
+
+    #[test]
+    fn framing_chunked_only() {
+        let te = test_decode::<TransferEncoding>(&["gzip, chunked"]).unwrap();
+        assert!(validate_framing(Some(&te), None).is_ok());
+        assert!(validate_framing(None, Some(&ContentLength(10))).is_ok());
+        assert!(validate_framing(None, None).is_ok());
+    }
+
+    #[test]
+    fn framing_with_content_length() {
+        let te = TransferEncoding::chunked();
+        assert!(validate_framing(Some(&te), Some(&ContentLength(10))).is_err());
+    }
+
+    #[test]
+    fn framing_chunked_order() {
+        for s in &[
+            "chunked, gzip",
+            "chunked, chunked",
+            "chunked, gzip, chunked",
+        ] {
+            let te = test_decode::<TransferEncoding>(&[s]).unwrap();
+            assert!(validate_framing(Some(&te), None).is_err(), "{:?}", s);
+        }
+
+        let te = test_decode::<TransferEncoding>(&["gzip"]).unwrap();
+        assert!(validate_framing(Some(&te), None).is_ok());
+    }
 
     #[test]
     fn chunked_is_chunked() {
@@ -1,7 +1,7 @@
 use std::fmt;
 use std::str::FromStr;
 
-use crate::util::HeaderValueString;
+use crate::util::{self, HeaderValueString};
 
 /// `User-Agent` header, defined in
 /// [RFC7231](https://datatracker.ietf.org/doc/html/rfc7231#section-5.5.3)
@@ -29,7 +29,7 @@ use crate::util::HeaderValueString;
 ///
 /// # Notes
 ///
-/// * The parser does not split the value
+/// * The value is kept as sent, [`products`](UserAgent::products) splits it
 ///
 /// # Example
 ///
@@ -60,6 +60,13 @@ impl UserAgent {
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Iterate the products, as `(product, version)` pairs.
+    ///
+    /// Comments, such as `(Ubuntu)`, are skipped.
+    pub fn products(&self) -> impl Iterator<Item = (&str, Option<&str>)> + '_ {
+        util::products(self.as_str())
+    }
 }
 
 error_type!(InvalidUserAgent);
@@ -67,9 +74,9 @@ error_type!(InvalidUserAgent);
 impl FromStr for UserAgent {
     type Err = InvalidUserAgent;
     fn from_str(src: &str) -> Result<Self, Self::Err> {
-        HeaderValueString::from_str(src)
+        HeaderValueString::trimmed(src)
             .map(UserAgent)
-            .map_err(|_| InvalidUserAgent { _inner: () })
+            .ok_or(InvalidUserAgent { _inner: () })
     }
 }
 
@@ -78,3 +85,31 @@ impl fmt::Display for UserAgent {
         fmt::Display::fmt(&self.0, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_trims() {
+        let val = " curl/8.4.0 ".parse::<UserAgent>().unwrap();
+        assert_eq!(val.as_str(), "curl/8.4.0");
+    }
+
+    #[test]
+    fn products() {
+        let val = "CERN-LineMode/2.15 libwww/2.17b3"
+            .parse::<UserAgent>()
+            .unwrap();
+        assert_eq!(
+            val.products().collect::<Vec<_>>(),
+            [("CERN-LineMode", Some("2.15")), ("libwww", Some("2.17b3"))]
+        );
+    }
+
+    #[test]
+    fn from_str_empty() {
+        assert!("".parse::<UserAgent>().is_err());
+        assert!("  ".parse::<UserAgent>().is_err());
+    }
+}
@@ -1,6 +1,9 @@
-use http::{HeaderName, HeaderValue};
+use std::iter::FromIterator;
+
+use http::{HeaderMap, HeaderName, HeaderValue};
 
 use crate::util::FlatCsv;
+use crate::{AcceptEncoding, Encoding, HeaderMapExt};
 
 /// `Vary` header, defined in [RFC7231](https://tools.ietf.org/html/rfc7231#section-7.1.4)
 ///
@@ -51,6 +54,126 @@ impl Vary {
     pub fn iter_strs(&self) -> impl Iterator<Item = &str> {
         self.0.iter()
     }
+
+    /// Lowercase and de-duplicate the header names, keeping the order they
+    /// first appear in.
+    ///
+    /// If `*` is included, this collapses to just `Vary: *`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{HeaderMap, HeaderMapExt, Vary};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("vary", "Accept-Encoding, origin, accept-encoding".parse().unwrap());
+    /// let mut vary = headers.typed_get::<Vary>().unwrap();
+    ///
+    /// vary.normalize();
+    /// assert_eq!(vary.iter_strs().collect::<Vec<_>>(), ["accept-encoding", "origin"]);
+    /// ```
+    pub fn normalize(&mut self) {
+        let names = self.iter_strs().map(str::to_ascii_lowercase);
+        *self = Vary::from_names(names);
+    }
+
+    fn from_names<I: Iterator<Item = String>>(names: I) -> Vary {
+        let mut unique: Vec<String> = Vec::new();
+        for name in names {
+            if name == "*" {
+                return Vary::any();
+            }
+            if !name.is_empty() && !unique.contains(&name) {
+                unique.push(name);
+            }
+        }
+        let value =
+            HeaderValue::from_str(&unique.join(", ")).expect("header names are valid HeaderValues");
+        Vary(value.into())
+    }
+
+    /// The key returned by [`cache_key`](Vary::cache_key) for `Vary: *`,
+    /// meaning the response can't be cached by varying headers.
+    pub const UNCACHEABLE: &'static str = "*";
+
+    /// Compute a cache key out of the request headers this `Vary` lists.
+    ///
+    /// Requests that should get the same cached response get the same key:
+    /// header names are compared case-insensitively and in any order, and
+    /// `Accept-Encoding` is compared by the effective quality of each
+    /// encoding, not how it was written. A missing header gives a different
+    /// key than an empty one.
+    ///
+    /// Returns [`Vary::UNCACHEABLE`] if this is `Vary: *`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{HeaderMap, Vary};
+    ///
+    /// let vary = Vary::from(http::header::ACCEPT_ENCODING);
+    ///
+    /// let mut a = HeaderMap::new();
+    /// a.insert("accept-encoding", "gzip, br".parse().unwrap());
+    /// let mut b = HeaderMap::new();
+    /// b.insert("accept-encoding", "br,gzip;q=1.0".parse().unwrap());
+    ///
+    /// assert_eq!(vary.cache_key(&a), vary.cache_key(&b));
+    /// ```
+    pub fn cache_key(&self, req: &HeaderMap) -> String {
+        if self.is_any() {
+            return Vary::UNCACHEABLE.to_owned();
+        }
+
+        let mut names = self
+            .iter_strs()
+            .filter(|name| !name.is_empty())
+            .map(|name| name.to_ascii_lowercase())
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+
+        // Header values can't contain a newline, so it can't be ambiguous.
+        names
+            .iter()
+            .map(|name| match cache_key_value(name, req) {
+                Some(value) => format!("{}={}", name, value),
+                None => name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn cache_key_value(name: &str, req: &HeaderMap) -> Option<String> {
+    let mut values = req.get_all(name).iter().peekable();
+    values.peek()?;
+
+    if name == "accept-encoding" {
+        if let Some(accept_enc) = req.typed_get::<AcceptEncoding>() {
+            let mut items = accept_enc.to_vec();
+            items.sort_by(|a, b| {
+                b.quality()
+                    .cmp(&a.quality())
+                    .then_with(|| a.value().as_str().cmp(b.value().as_str()))
+            });
+
+            let mut seen: Vec<Encoding> = Vec::new();
+            let mut normalized = Vec::new();
+            for item in items {
+                if !seen.contains(item.value()) {
+                    seen.push(item.value().clone());
+                    normalized.push(item.to_string());
+                }
+            }
+            return Some(normalized.join(","));
+        }
+    }
+
+    let values = values
+        .map(|value| String::from_utf8_lossy(value.as_bytes()).trim().to_owned())
+        .collect::<Vec<_>>();
+    Some(values.join(","))
 }
 
 impl From<HeaderName> for Vary {
@@ -59,6 +182,17 @@ impl From<HeaderName> for Vary {
     }
 }
 
+/// Collects the names like [`normalize`](Vary::normalize) does, removing
+/// duplicates and collapsing to `Vary: *` if it's included.
+impl FromIterator<HeaderName> for Vary {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = HeaderName>,
+    {
+        Vary::from_names(iter.into_iter().map(|name| name.as_str().to_owned()))
+    }
+}
+
 /*
 test_vary {
     test_header!(test1, vec![b"accept-encoding, accept-language"]);
@@ -80,10 +214,110 @@ test_vary {
 
 #[cfg(test)]
 mod tests {
+    use super::super::{test_decode, test_encode};
     use super::*;
 
     #[test]
     fn any_is_any() {
         assert!(Vary::any().is_any());
     }
+
+    #[test]
+    fn from_iter_dedup() {
+        let vary = vec![
+            ::http::header::ACCEPT_ENCODING,
+            ::http::header::ORIGIN,
+            HeaderName::from_static("accept-encoding"),
+        ]
+        .into_iter()
+        .collect::<Vary>();
+
+        let headers = test_encode(vary);
+        assert_eq!(headers["vary"], "accept-encoding, origin");
+    }
+
+    #[test]
+    fn from_iter_wildcard_collapses() {
+        let vary = vec![
+            ::http::header::ACCEPT_ENCODING,
+            HeaderName::from_static("*"),
+            ::http::header::ORIGIN,
+        ]
+        .into_iter()
+        .collect::<Vary>();
+
+        let headers = test_encode(vary);
+        assert_eq!(headers["vary"], "*");
+    }
+
+    #[test]
+    fn normalize() {
+        let mut vary =
+            test_decode::<Vary>(&["Accept-Encoding, ORIGIN", "origin, accept-encoding"]).unwrap();
+        vary.normalize();
+        assert_eq!(
+            vary.iter_strs().collect::<Vec<_>>(),
+            ["accept-encoding", "origin"]
+        );
+
+        let mut vary = test_decode::<Vary>(&["Accept-Encoding", "*"]).unwrap();
+        vary.normalize();
+        assert_eq!(vary, Vary::any());
+    }
+
+    fn request(headers: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for &(name, value) in headers {
+            map.append(name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    #[test]
+    fn cache_key_accept_encoding_reordered() {
+        let vary = Vary::from(::http::header::ACCEPT_ENCODING);
+
+        let a = vary.cache_key(&request(&[("accept-encoding", "gzip, br;q=0.5, deflate")]));
+        let b = vary.cache_key(&request(&[("accept-encoding", "deflate,GZIP ,br; q=0.50")]));
+        let c = vary.cache_key(&request(&[
+            ("accept-encoding", "deflate"),
+            ("accept-encoding", "br;q=0.5, gzip"),
+        ]));
+
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+        assert_eq!(a, "accept-encoding=deflate,gzip,br; q=0.5");
+    }
+
+    #[test]
+    fn cache_key_distinguishes_values() {
+        let vary = Vary::from(::http::header::ACCEPT_ENCODING);
+
+        let gzip = vary.cache_key(&request(&[("accept-encoding", "gzip")]));
+        let br = vary.cache_key(&request(&[("accept-encoding", "br")]));
+        let empty = vary.cache_key(&request(&[("accept-encoding", "")]));
+        let missing = vary.cache_key(&request(&[]));
+
+        assert_ne!(gzip, br);
+        assert_ne!(empty, missing);
+    }
+
+    #[test]
+    fn cache_key_multiple_headers() {
+        let a = Vary(HeaderValue::from_static("Accept-Language, accept-encoding").into());
+        let b = Vary(HeaderValue::from_static("accept-encoding, ACCEPT-LANGUAGE").into());
+        let req = request(&[("accept-language", "en"), ("accept-encoding", "gzip")]);
+
+        assert_eq!(a.cache_key(&req), b.cache_key(&req));
+        assert_eq!(
+            a.cache_key(&req),
+            "accept-encoding=gzip\naccept-language=en"
+        );
+    }
+
+    #[test]
+    fn cache_key_any() {
+        let req = request(&[("accept-encoding", "gzip")]);
+        assert_eq!(Vary::any().cache_key(&req), Vary::UNCACHEABLE);
+    }
 }
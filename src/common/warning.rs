@@ -0,0 +1,196 @@
+use http::{HeaderName, HeaderValue};
+
+use crate::util::FlatCsv;
+use crate::{Error, Header};
+
+/// `Warning` header, defined in [RFC7234](https://tools.ietf.org/html/rfc7234#section-5.5)
+///
+/// The `Warning` header field can be used to carry additional information
+/// about the status or transformation of a message that might not be reflected
+/// in the status code. This header is sometimes used as backwards
+/// compatible way to notify of a deprecated API.
+///
+/// [RFC9111](https://www.rfc-editor.org/rfc/rfc9111#section-5.5) obsoletes
+/// it, but older caches still send it.
+///
+/// # ABNF
+///
+/// ```text
+/// Warning       = 1#warning-value
+/// warning-value = warn-code SP warn-agent SP warn-text
+///                                       [ SP warn-date ]
+/// warn-code  = 3DIGIT
+/// warn-agent = ( uri-host [ ":" port ] ) / pseudonym
+///                 ; the name or pseudonym of the server adding
+///                 ; the Warning header field, for use in debugging
+///                 ; a single "-" is recommended when agent unknown
+/// warn-text  = quoted-string
+/// warn-date  = DQUOTE HTTP-date DQUOTE
+/// ```
+///
+/// # Example values
+///
+/// * `112 - "network down" "Sat, 25 Aug 2012 23:34:45 GMT"`
+/// * `299 - "Deprecated API " "Tue, 15 Nov 1994 08:12:31 GMT"`
+/// * `299 api.hyper.rs:8080 "Deprecated API : use newapi.hyper.rs instead."`
+/// * `110 - "Response is Stale", 113 - "Heuristic Expiration"`
+///
+/// # Examples
+///
+/// ```
+/// use headers::{HeaderMap, HeaderMapExt, Warning};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.typed_insert(Warning::new(299, "api.hyper.rs", "Deprecated").unwrap());
+///
+/// assert_eq!(headers["warning"], "299 api.hyper.rs \"Deprecated\"");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Warning(FlatCsv);
+
+impl Warning {
+    /// Create a `Warning` header with a single warning, without a date.
+    ///
+    /// Fails if `code` doesn't have 3 digits, if `agent` is empty or
+    /// contains whitespace, quotes or commas, or if `text` contains bytes
+    /// not allowed in a header value.
+    pub fn new(code: u16, agent: &str, text: &str) -> Result<Warning, Error> {
+        let valid_agent = !agent.is_empty()
+            && !agent
+                .bytes()
+                .any(|b| b.is_ascii_whitespace() || b == b'"' || b == b',');
+        if !(100..=999).contains(&code) || !valid_agent {
+            return Err(Error::invalid());
+        }
+
+        let text = text.replace('\\', "\\\\").replace('"', "\\\"");
+        let value = HeaderValue::from_str(&format!("{} {} \"{}\"", code, agent, text))
+            .map_err(|_| Error::invalid())?;
+        Ok(Warning(value.into()))
+    }
+
+    /// Iterate the warn-codes of the listed warnings.
+    pub fn codes(&self) -> impl Iterator<Item = u16> + '_ {
+        self.0.iter().filter_map(parse_code)
+    }
+
+    /// Whether any of the warnings marks the response as stale.
+    ///
+    /// That is the case for the codes `110` (Response is Stale) and `111`
+    /// (Revalidation Failed), which caches attach to a stored response.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{HeaderMap, HeaderMapExt, Warning};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("warning", "110 - \"Response is Stale\"".parse().unwrap());
+    /// let warning = headers.typed_get::<Warning>().unwrap();
+    ///
+    /// assert!(warning.is_stale_response());
+    /// ```
+    pub fn is_stale_response(&self) -> bool {
+        self.codes().any(|code| code == 110 || code == 111)
+    }
+}
+
+/// The warn-code of `entry`, if it starts with a code, an agent and a
+/// quoted warn-text.
+fn parse_code(entry: &str) -> Option<u16> {
+    let (code, rest) = entry.split_once(' ')?;
+    let (agent, text) = rest.split_once(' ')?;
+    if code.len() != 3 || agent.is_empty() || !text.starts_with('"') {
+        return None;
+    }
+    if !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    code.parse().ok()
+}
+
+impl Header for Warning {
+    fn name() -> &'static HeaderName {
+        &::http::header::WARNING
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, Error> {
+        let flat = values.collect::<FlatCsv>();
+        let valid = {
+            let mut entries = flat.iter().filter(|entry| !entry.is_empty()).peekable();
+            entries.peek().is_some() && entries.all(|entry| parse_code(entry).is_some())
+        };
+        if !valid {
+            return Err(Error::invalid());
+        }
+        Ok(Warning(flat))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let warning =
+            test_decode::<Warning>(&["112 - \"network down\" \"Sat, 25 Aug 2012 23:34:45 GMT\""])
+                .unwrap();
+        assert_eq!(warning.codes().collect::<Vec<_>>(), [112]);
+
+        let warning = test_decode::<Warning>(&[
+            "299 api.hyper.rs:8080 \"Deprecated API, use newapi.hyper.rs instead.\"",
+            "113 - \"Heuristic Expiration\"",
+        ])
+        .unwrap();
+        assert_eq!(warning.codes().collect::<Vec<_>>(), [299, 113]);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Warning>(&[""]), None);
+        assert_eq!(test_decode::<Warning>(&["99 - \"short code\""]), None);
+        assert_eq!(test_decode::<Warning>(&["299 - unquoted"]), None);
+        assert_eq!(test_decode::<Warning>(&["110 - \"ok\", nonsense"]), None);
+    }
+
+    #[test]
+    fn is_stale_response() {
+        let warning = test_decode::<Warning>(&["110 - \"Response is Stale\""]).unwrap();
+        assert!(warning.is_stale_response());
+
+        let warning =
+            test_decode::<Warning>(&["199 - \"Miscellaneous\", 111 - \"Revalidation Failed\""])
+                .unwrap();
+        assert!(warning.is_stale_response());
+
+        let warning = test_decode::<Warning>(&["199 - \"Miscellaneous Warning\""]).unwrap();
+        assert!(!warning.is_stale_response());
+    }
+
+    #[test]
+    fn new() {
+        let warning = Warning::new(199, "-", "say \"hi\"").unwrap();
+        assert!(!warning.is_stale_response());
+
+        let headers = test_encode(warning);
+        assert_eq!(headers["warning"], "199 - \"say \\\"hi\\\"\"");
+        assert_eq!(
+            test_decode::<Warning>(&[headers["warning"].to_str().unwrap()])
+                .unwrap()
+                .codes()
+                .collect::<Vec<_>>(),
+            [199]
+        );
+
+        assert!(Warning::new(99, "-", "short code").is_err());
+        assert!(Warning::new(1000, "-", "long code").is_err());
+        assert!(Warning::new(199, "two words", "x").is_err());
+        assert!(Warning::new(199, "-", "line\nbreak").is_err());
+    }
+}
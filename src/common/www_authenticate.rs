@@ -0,0 +1,201 @@
+use bytes::{BufMut, BytesMut};
+use http::HeaderValue;
+
+use crate::util::FlatCsv;
+
+/// `WWW-Authenticate` header, defined in
+/// [RFC7235](https://tools.ietf.org/html/rfc7235#section-4.1)
+///
+/// The `WWW-Authenticate` header field indicates the authentication
+/// scheme(s) and parameters applicable to the target resource. A server
+/// generating a 401 (Unauthorized) response MUST send a WWW-Authenticate
+/// header field containing at least one challenge.
+///
+/// # ABNF
+///
+/// ```text
+/// WWW-Authenticate = 1#challenge
+/// challenge        = auth-scheme [ 1*SP ( token68 / #auth-param ) ]
+/// auth-param       = token BWS "=" BWS ( token / quoted-string )
+/// ```
+///
+/// # Example values
+/// * `Basic realm="simple"`
+/// * `Bearer realm="api", error="invalid_token"`
+///
+/// # Examples
+///
+/// ```
+/// use headers::WwwAuthenticate;
+///
+/// let challenge = WwwAuthenticate::bearer("api")
+///     .unwrap()
+///     .with_param("error", "invalid_token")
+///     .unwrap();
+///
+/// assert_eq!(challenge.scheme(), Some("Bearer"));
+/// assert_eq!(challenge.param("error"), Some("invalid_token"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct WwwAuthenticate(FlatCsv);
+
+derive_header! {
+    WwwAuthenticate(_),
+    name: WWW_AUTHENTICATE
+}
+
+error_type!(InvalidWwwAuthenticate);
+
+impl WwwAuthenticate {
+    /// Create a `Basic` challenge for the given realm.
+    pub fn basic(realm: &str) -> Result<Self, InvalidWwwAuthenticate> {
+        Self::challenge("Basic", realm)
+    }
+
+    /// Create a `Bearer` challenge for the given realm.
+    ///
+    /// Further parameters, such as `error` or `scope` from
+    /// [RFC6750](https://tools.ietf.org/html/rfc6750#section-3), can be
+    /// added with [`WwwAuthenticate::with_param`].
+    pub fn bearer(realm: &str) -> Result<Self, InvalidWwwAuthenticate> {
+        Self::challenge("Bearer", realm)
+    }
+
+    fn challenge(scheme: &'static str, realm: &str) -> Result<Self, InvalidWwwAuthenticate> {
+        let mut buf = BytesMut::from(scheme.as_bytes());
+        buf.put_u8(b' ');
+        put_param(&mut buf, "realm", realm)?;
+        to_value(buf).map(|val| WwwAuthenticate(val.into()))
+    }
+
+    /// Append an auth-param to the last challenge of this header.
+    ///
+    /// The `value` is always sent as a quoted-string.
+    pub fn with_param(self, name: &str, value: &str) -> Result<Self, InvalidWwwAuthenticate> {
+        let mut buf = BytesMut::from(self.0.value.as_bytes());
+        buf.extend_from_slice(b", ");
+        put_param(&mut buf, name, value)?;
+        to_value(buf).map(|val| WwwAuthenticate(val.into()))
+    }
+
+    /// Get the auth-scheme of the first challenge.
+    pub fn scheme(&self) -> Option<&str> {
+        let first = self.0.iter().next()?;
+        first.split(' ').next().filter(|s| !s.is_empty())
+    }
+
+    /// Get the value of an auth-param of the first challenge.
+    ///
+    /// Parameter names are compared case-insensitively. Surrounding quotes
+    /// are removed from quoted-string values, but escapes are left as-is.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        let mut items = self.0.iter();
+        let first = items.next()?;
+        let (_scheme, first) = first.split_once(' ')?;
+
+        ::std::iter::once(first)
+            .chain(items.take_while(|item| !starts_new_challenge(item)))
+            .filter_map(|item| {
+                let mut kv = item.splitn(2, '=');
+                let key = kv.next()?.trim();
+                let val = kv.next()?.trim();
+                Some((key, val))
+            })
+            .find(|&(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, val)| unquote(val))
+    }
+}
+
+// `Basic realm="a", Bearer realm="b"` lists two challenges, and the second
+// one starts with an auth-scheme followed by a space.
+fn starts_new_challenge(item: &str) -> bool {
+    match (item.find(' '), item.find('=')) {
+        (Some(space), Some(eq)) => space < eq,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+fn unquote(val: &str) -> &str {
+    if val.len() >= 2 && val.starts_with('"') && val.ends_with('"') {
+        &val[1..val.len() - 1]
+    } else {
+        val
+    }
+}
+
+fn put_param(buf: &mut BytesMut, name: &str, value: &str) -> Result<(), InvalidWwwAuthenticate> {
+    if name.is_empty() || !name.bytes().all(is_tchar) {
+        return Err(InvalidWwwAuthenticate { _inner: () });
+    }
+
+    buf.extend_from_slice(name.as_bytes());
+    buf.extend_from_slice(b"=\"");
+    for b in value.bytes() {
+        if b == b'"' || b == b'\\' {
+            buf.put_u8(b'\\');
+        }
+        buf.put_u8(b);
+    }
+    buf.put_u8(b'"');
+    Ok(())
+}
+
+fn to_value(buf: BytesMut) -> Result<HeaderValue, InvalidWwwAuthenticate> {
+    HeaderValue::from_maybe_shared(buf.freeze()).map_err(|_| InvalidWwwAuthenticate { _inner: () })
+}
+
+fn is_tchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn bearer_challenge() {
+        let challenge = WwwAuthenticate::bearer("api")
+            .unwrap()
+            .with_param("error", "invalid_token")
+            .unwrap();
+
+        let headers = test_encode(challenge);
+        assert_eq!(
+            headers["www-authenticate"],
+            "Bearer realm=\"api\", error=\"invalid_token\""
+        );
+    }
+
+    #[test]
+    fn decode_params() {
+        let challenge = test_decode::<WwwAuthenticate>(&[
+            "Bearer realm=\"api\", error=\"invalid_token\"",
+            "Basic realm=\"fallback\"",
+        ])
+        .unwrap();
+
+        assert_eq!(challenge.scheme(), Some("Bearer"));
+        assert_eq!(challenge.param("realm"), Some("api"));
+        assert_eq!(challenge.param("Error"), Some("invalid_token"));
+        assert_eq!(challenge.param("scope"), None);
+    }
+
+    #[test]
+    fn invalid_param_name() {
+        let challenge = WwwAuthenticate::bearer("api").unwrap();
+        assert!(challenge.with_param("bad name", "x").is_err());
+    }
+
+    #[test]
+    fn quoted_value_is_escaped() {
+        let challenge = WwwAuthenticate::basic("say \"hi\"").unwrap();
+
+        let headers = test_encode(challenge);
+        assert_eq!(
+            headers["www-authenticate"],
+            "Basic realm=\"say \\\"hi\\\"\""
+        );
+    }
+}
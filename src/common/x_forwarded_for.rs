@@ -0,0 +1,273 @@
+use std::net::{IpAddr, SocketAddr};
+
+use http::{HeaderName, HeaderValue};
+
+use crate::{Error, Header};
+
+static X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+
+/// `X-Forwarded-For` header.
+///
+/// The de-facto standard header for identifying the originating IP address
+/// of a client connecting through proxies. Every proxy appends the address
+/// it received the request from, so the leftmost address is the original
+/// client and the rightmost one is the address the closest proxy saw.
+///
+/// Since any client can send this header, only the entries appended by
+/// proxies you trust should be relied upon, which usually means reading it
+/// from the right with [`from_right`](XForwardedFor::from_right).
+///
+/// # Parsing
+///
+/// Entries may carry a port (`203.0.113.7:5000`, `[2001:db8::1]:443`) and
+/// IPv6 addresses may be bracketed (`[2001:db8::1]`); the port and brackets
+/// are dropped. Entries that are not IP addresses, such as `unknown` or
+/// obfuscated identifiers like `_hidden`, keep their position as `None`, so
+/// counting from the right still lands on the entry a proxy appended. They
+/// are encoded as `unknown`. A header without any entry fails to decode.
+///
+/// # Example values
+/// * `203.0.113.195`
+/// * `203.0.113.195, 2001:db8:85a3:8d3:1319:8a2e:370:7348`
+/// * `203.0.113.195:41237, [2001:db8::1]:443, unknown`
+///
+/// # Examples
+///
+/// ```
+/// use std::net::IpAddr;
+/// use headers::{HeaderMap, HeaderMapExt, XForwardedFor};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("x-forwarded-for", "203.0.113.195, 198.51.100.1".parse().unwrap());
+/// let xff = headers.typed_get::<XForwardedFor>().unwrap();
+///
+/// assert_eq!(xff.leftmost(), "203.0.113.195".parse::<IpAddr>().ok());
+/// assert_eq!(xff.rightmost(), "198.51.100.1".parse::<IpAddr>().ok());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct XForwardedFor(Vec<Option<IpAddr>>);
+
+impl XForwardedFor {
+    /// Create an `X-Forwarded-For` from addresses, from the client to the
+    /// closest proxy.
+    ///
+    /// Fails if there is no address, since the header needs at least one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::net::IpAddr;
+    /// use headers::XForwardedFor;
+    ///
+    /// let client = "203.0.113.195".parse::<IpAddr>().unwrap();
+    /// assert!(XForwardedFor::try_from_iter(vec![client]).is_ok());
+    /// assert!(XForwardedFor::try_from_iter(Vec::new()).is_err());
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = IpAddr>,
+    {
+        let addrs = iter.into_iter().map(Some).collect::<Vec<_>>();
+        if addrs.is_empty() {
+            Err(Error::invalid())
+        } else {
+            Ok(XForwardedFor(addrs))
+        }
+    }
+
+    /// The address of the original client, as claimed by the first proxy,
+    /// if that entry is an IP address.
+    pub fn leftmost(&self) -> Option<IpAddr> {
+        self.0[0]
+    }
+
+    /// The address the closest proxy received the request from, if that
+    /// entry is an IP address.
+    pub fn rightmost(&self) -> Option<IpAddr> {
+        self.from_right(0)
+    }
+
+    /// The entry `hops` positions from the right, where `0` is the
+    /// rightmost one.
+    ///
+    /// Returns `None` if there are not that many entries, or if the entry
+    /// isn't an IP address.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::net::IpAddr;
+    /// use headers::{HeaderMap, HeaderMapExt, XForwardedFor};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("x-forwarded-for", "198.51.100.1, 203.0.113.195, unknown".parse().unwrap());
+    /// let xff = headers.typed_get::<XForwardedFor>().unwrap();
+    ///
+    /// // Behind two trusted proxies, the first one appended `unknown`.
+    /// assert_eq!(xff.from_right(0), None);
+    /// assert_eq!(xff.from_right(1), "203.0.113.195".parse::<IpAddr>().ok());
+    /// ```
+    pub fn from_right(&self, hops: usize) -> Option<IpAddr> {
+        let idx = self.0.len().checked_sub(hops + 1)?;
+        self.0[idx]
+    }
+
+    /// Iterate the entries, from the client to the closest proxy, with
+    /// `None` for entries that aren't IP addresses.
+    pub fn iter(&self) -> impl Iterator<Item = Option<IpAddr>> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// Get the entries, from the client to the closest proxy, with `None`
+    /// for entries that aren't IP addresses.
+    pub fn as_slice(&self) -> &[Option<IpAddr>] {
+        &self.0
+    }
+}
+
+fn parse_entry(entry: &str) -> Option<IpAddr> {
+    let entry = entry.trim();
+    if let Ok(addr) = entry.parse::<IpAddr>() {
+        return Some(addr);
+    }
+    if let Ok(addr) = entry.parse::<SocketAddr>() {
+        return Some(addr.ip());
+    }
+    if entry.starts_with('[') && entry.ends_with(']') {
+        return entry[1..entry.len() - 1].parse().ok();
+    }
+    None
+}
+
+impl Header for XForwardedFor {
+    fn name() -> &'static HeaderName {
+        &X_FORWARDED_FOR
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, Error> {
+        let mut addrs = Vec::new();
+        for value in values {
+            let value = value.to_str().map_err(|_| Error::invalid())?;
+            addrs.extend(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|entry| !entry.is_empty())
+                    .map(parse_entry),
+            );
+        }
+
+        if addrs.is_empty() {
+            Err(Error::invalid())
+        } else {
+            Ok(XForwardedFor(addrs))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let s = self
+            .0
+            .iter()
+            .map(|addr| match addr {
+                Some(addr) => addr.to_string(),
+                None => "unknown".to_owned(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let value = HeaderValue::from_str(&s).expect("IP addresses are valid HeaderValues");
+        values.extend(::std::iter::once(value));
+    }
+}
+
+impl From<IpAddr> for XForwardedFor {
+    fn from(addr: IpAddr) -> XForwardedFor {
+        XForwardedFor(vec![Some(addr)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn mixed_chain() {
+        let xff = test_decode::<XForwardedFor>(&[
+            "203.0.113.195, 2001:db8:85a3:8d3:1319:8a2e:370:7348",
+            "198.51.100.17",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            xff.iter().collect::<Vec<_>>(),
+            vec![
+                Some(ip("203.0.113.195")),
+                Some(ip("2001:db8:85a3:8d3:1319:8a2e:370:7348")),
+                Some(ip("198.51.100.17")),
+            ]
+        );
+        assert_eq!(xff.leftmost(), Some(ip("203.0.113.195")));
+        assert_eq!(xff.rightmost(), Some(ip("198.51.100.17")));
+    }
+
+    #[test]
+    fn ports_brackets_and_unknown() {
+        let xff = test_decode::<XForwardedFor>(&[
+            "203.0.113.7:5000, [2001:db8::1], unknown, [2001:db8::2]:443, _hidden",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            xff.as_slice(),
+            &[
+                Some(ip("203.0.113.7")),
+                Some(ip("2001:db8::1")),
+                None,
+                Some(ip("2001:db8::2")),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn from_right_keeps_positions() {
+        let xff = test_decode::<XForwardedFor>(&["198.51.100.1, 203.0.113.7, unknown"]).unwrap();
+
+        assert_eq!(xff.rightmost(), None);
+        assert_eq!(xff.from_right(0), None);
+        assert_eq!(xff.from_right(1), Some(ip("203.0.113.7")));
+        assert_eq!(xff.from_right(2), Some(ip("198.51.100.1")));
+        assert_eq!(xff.from_right(3), None);
+    }
+
+    #[test]
+    fn no_entries() {
+        assert_eq!(test_decode::<XForwardedFor>(&[""]), None);
+        assert_eq!(test_decode::<XForwardedFor>(&[" , "]), None);
+
+        let xff = test_decode::<XForwardedFor>(&["unknown"]).unwrap();
+        assert_eq!(xff.as_slice(), &[None]);
+    }
+
+    #[test]
+    fn encode() {
+        let xff =
+            XForwardedFor::try_from_iter(vec![ip("203.0.113.195"), ip("2001:db8::1")]).unwrap();
+
+        let headers = test_encode(xff);
+        assert_eq!(headers["x-forwarded-for"], "203.0.113.195, 2001:db8::1");
+
+        let xff = test_decode::<XForwardedFor>(&["_hidden, 203.0.113.195"]).unwrap();
+        let headers = test_encode(xff);
+        assert_eq!(headers["x-forwarded-for"], "unknown, 203.0.113.195");
+    }
+
+    #[test]
+    fn try_from_iter_empty() {
+        assert!(XForwardedFor::try_from_iter(Vec::new()).is_err());
+    }
+}
@@ -91,3 +91,6 @@ mod map_ext;
 
 pub use self::common::*;
 pub use self::map_ext::HeaderMapExt;
+pub use self::util::{
+    best_encoding, CsvList, Encoding, InvalidQualityValue, Quality, QualityValue,
+};
@@ -8,11 +8,31 @@ pub trait HeaderMapExt: self::sealed::Sealed {
         H: Header;
 
     /// Tries to find the header by name, and then decode it into `H`.
+    ///
+    /// Returns `None` both if the header is missing and if it is invalid.
+    /// Use [`typed_try_get`](HeaderMapExt::typed_try_get) to tell those
+    /// apart.
     fn typed_get<H>(&self) -> Option<H>
     where
         H: Header;
 
     /// Tries to find the header by name, and then decode it into `H`.
+    ///
+    /// Returns `Ok(None)` if the header is missing, and an error if it is
+    /// present but could not be decoded. The error's `Display` names the
+    /// header and its values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{ContentLength, HeaderMap, HeaderMapExt};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// assert!(headers.typed_try_get::<ContentLength>().unwrap().is_none());
+    ///
+    /// headers.insert("content-length", "nope".parse().unwrap());
+    /// assert!(headers.typed_try_get::<ContentLength>().is_err());
+    /// ```
     fn typed_try_get<H>(&self) -> Result<Option<H>, Error>
     where
         H: Header;
@@ -41,11 +61,14 @@ impl HeaderMapExt for http::HeaderMap {
     where
         H: Header,
     {
-        let mut values = self.get_all(H::name()).iter();
+        let all = self.get_all(H::name());
+        let mut values = all.iter();
         if values.size_hint() == (0, Some(0)) {
             Ok(None)
         } else {
-            H::decode(&mut values).map(Some)
+            H::decode(&mut values)
+                .map(Some)
+                .map_err(|err| err.with_header(H::name(), all.iter()))
         }
     }
 }
@@ -85,3 +108,54 @@ mod sealed {
     pub trait Sealed {}
     impl Sealed for ::http::HeaderMap {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::HeaderMapExt;
+    use crate::{AcceptEncoding, ContentLength, Encoding, Range};
+
+    #[test]
+    fn typed_try_get_absent() {
+        let headers = http::HeaderMap::new();
+
+        assert!(headers.typed_try_get::<AcceptEncoding>().unwrap().is_none());
+        assert!(headers.typed_try_get::<Range>().unwrap().is_none());
+    }
+
+    #[test]
+    fn typed_try_get_valid() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert("accept-encoding", "gzip, br".parse().unwrap());
+        headers.insert("content-length", "42".parse().unwrap());
+
+        let accept_enc = headers.typed_try_get::<AcceptEncoding>().unwrap().unwrap();
+        assert!(accept_enc.accepts(&Encoding::Gzip));
+        assert_eq!(
+            headers.typed_try_get::<ContentLength>().unwrap(),
+            Some(ContentLength(42))
+        );
+    }
+
+    #[test]
+    fn typed_try_get_invalid() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert("content-length", "forty-two".parse().unwrap());
+        headers.insert("range", "pages=1-2".parse().unwrap());
+
+        assert!(headers.typed_try_get::<ContentLength>().is_err());
+        assert!(headers.typed_try_get::<Range>().is_err());
+        assert!(headers.typed_get::<ContentLength>().is_none());
+    }
+
+    #[test]
+    fn typed_try_get_error_display() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert("content-length", "forty-two".parse().unwrap());
+
+        let err = headers.typed_try_get::<ContentLength>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid HTTP header content-length: \"forty-two\""
+        );
+    }
+}
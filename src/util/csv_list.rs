@@ -0,0 +1,132 @@
+use std::fmt;
+use std::iter::FromIterator;
+
+use http::HeaderValue;
+
+use super::FlatCsv;
+use crate::Error;
+
+/// A comma-separated list header value, to build custom list headers with.
+///
+/// Several header values are joined with `, `. Items are split on commas
+/// outside of quoted strings, with the surrounding whitespace trimmed, but
+/// are otherwise not interpreted: a `;q=` weight is part of the item.
+///
+/// # Example
+///
+/// ```
+/// use headers::{CsvList, HeaderValue};
+///
+/// let values = [
+///     HeaderValue::from_static("a, b=\"1,2\""),
+///     HeaderValue::from_static(" c "),
+/// ];
+/// let list = values.iter().collect::<CsvList>();
+///
+/// assert_eq!(list.iter().collect::<Vec<_>>(), ["a", "b=\"1,2\"", "c"]);
+/// assert_eq!(HeaderValue::from(&list), "a, b=\"1,2\",  c ");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CsvList(FlatCsv);
+
+impl CsvList {
+    /// Create a list from `items`, checking that each is allowed in a
+    /// header value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::CsvList;
+    ///
+    /// assert!(CsvList::try_from_iter(vec!["a", "b"]).is_ok());
+    /// assert!(CsvList::try_from_iter(vec!["a", "b\n"]).is_err());
+    /// ```
+    pub fn try_from_iter<'a, I>(items: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let values = items
+            .into_iter()
+            .map(|item| HeaderValue::from_str(item).map_err(|_| Error::invalid()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(CsvList(values.into_iter().collect()))
+    }
+
+    /// Iterate the items, trimmed of surrounding whitespace.
+    ///
+    /// Empty items, as in `a, , b`, are skipped. Nothing is yielded if the
+    /// value isn't visible ASCII.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter(|item| !item.is_empty())
+    }
+}
+
+/// # Panics
+///
+/// Panics if an item contains bytes not allowed in a header value. Use
+/// [`CsvList::try_from_iter`] to check instead.
+impl<'a> FromIterator<&'a str> for CsvList {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        CsvList::try_from_iter(iter).expect("invalid header value in CsvList")
+    }
+}
+
+impl<'a> FromIterator<&'a HeaderValue> for CsvList {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a HeaderValue>,
+    {
+        CsvList(iter.into_iter().collect())
+    }
+}
+
+impl From<HeaderValue> for CsvList {
+    fn from(value: HeaderValue) -> Self {
+        CsvList(value.into())
+    }
+}
+
+impl<'a> From<&'a CsvList> for HeaderValue {
+    fn from(list: &'a CsvList) -> HeaderValue {
+        (&list.0).into()
+    }
+}
+
+impl fmt::Debug for CsvList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iter_trims() {
+        let list = CsvList::from(HeaderValue::from_static(" a ,\tb,, \"c, d\" ,"));
+        assert_eq!(list.iter().collect::<Vec<_>>(), ["a", "b", "\"c, d\""]);
+    }
+
+    #[test]
+    fn from_iter() {
+        let list = vec!["a", "b;q=0.5"].into_iter().collect::<CsvList>();
+        assert_eq!(HeaderValue::from(&list), "a, b;q=0.5");
+        assert_eq!(list.iter().collect::<Vec<_>>(), ["a", "b;q=0.5"]);
+    }
+
+    #[test]
+    fn try_from_iter_rejects_invalid_bytes() {
+        assert!(CsvList::try_from_iter(vec!["a", "b\r\nc"]).is_err());
+        assert!(CsvList::try_from_iter(vec!["\0"]).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_iter_panics_on_invalid_bytes() {
+        let _ = vec!["a\n"].into_iter().collect::<CsvList>();
+    }
+}
@@ -0,0 +1,553 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str;
+
+use http::HeaderValue;
+
+use super::negotiation::{self, Specificity, Wildcard};
+use super::QualityValue;
+
+/// A value to represent an encoding used in `Transfer-Encoding`
+/// or `Accept-Encoding` header.
+///
+/// Parsing is case-insensitive, since content-codings are case-insensitive
+/// tokens. Anything following a `;` is not part of the coding name, and is
+/// ignored when parsing. Headers keep the raw value though, so parameters
+/// are written back unchanged when they are encoded again. The non-standard
+/// `brotli` is accepted as an alias of `br`, but always serialized as `br`.
+///
+/// # Example
+///
+/// ```
+/// use headers::Encoding;
+///
+/// let enc: Encoding = "gzip;level=9".parse().unwrap();
+/// assert_eq!(enc, Encoding::Gzip);
+/// assert_eq!(enc.to_string(), "gzip");
+/// ```
+// The order is arbitrary, it only allows sorting and using `BTreeMap`s.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Encoding {
+    /// The `chunked` encoding.
+    Chunked,
+    /// The `br` encoding.
+    Brotli,
+    /// The `gzip` encoding.
+    Gzip,
+    /// The `deflate` encoding.
+    Deflate,
+    /// The `compress` encoding.
+    Compress,
+    /// The `zstd` encoding.
+    Zstd,
+    /// The `identity` encoding.
+    Identity,
+    /// The `trailers` encoding.
+    Trailers,
+    /// Some other encoding that is less common, can be any String.
+    Ext(String),
+}
+
+static WELL_KNOWN: [Encoding; 8] = [
+    Encoding::Chunked,
+    Encoding::Brotli,
+    Encoding::Gzip,
+    Encoding::Deflate,
+    Encoding::Compress,
+    Encoding::Zstd,
+    Encoding::Identity,
+    Encoding::Trailers,
+];
+
+impl Encoding {
+    /// The longest coding name accepted when parsing.
+    ///
+    /// Registered codings are far shorter, so anything longer is rejected
+    /// instead of being kept as an `Encoding::Ext`.
+    pub const MAX_LEN: usize = 64;
+
+    /// The `identity` encoding, meaning no encoding was applied.
+    ///
+    /// This is the same as `Encoding::Identity`, as a single value to refer
+    /// to in negotiation code.
+    pub const IDENTITY: Encoding = Encoding::Identity;
+
+    /// The `*` of `Accept-Encoding` and `TE`, matching any encoding not
+    /// listed explicitly.
+    ///
+    /// This is an `Encoding::Ext`, which holds a `String`, so it can't be a
+    /// constant like [`IDENTITY`](Encoding::IDENTITY).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::Encoding;
+    ///
+    /// assert_eq!(Encoding::star(), "*".parse::<Encoding>().unwrap());
+    /// assert!(Encoding::star().is_star());
+    /// assert!(!Encoding::IDENTITY.is_star());
+    /// ```
+    pub fn star() -> Encoding {
+        Encoding::Ext(negotiation::ANY_TOKEN.to_owned())
+    }
+
+    /// Check if this is the `*` wildcard.
+    pub fn is_star(&self) -> bool {
+        self.is_wildcard()
+    }
+
+    /// All encodings with their own variant, so every variant except
+    /// `Encoding::Ext`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::Encoding;
+    ///
+    /// assert!(Encoding::well_known().contains(&Encoding::Zstd));
+    /// ```
+    pub fn well_known() -> &'static [Encoding] {
+        &WELL_KNOWN
+    }
+
+    /// View this `Encoding` as the token used in headers.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            Encoding::Chunked => "chunked",
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+            Encoding::Compress => "compress",
+            Encoding::Zstd => "zstd",
+            Encoding::Identity => "identity",
+            Encoding::Trailers => "trailers",
+            Encoding::Ext(ref s) => s.as_ref(),
+        }
+    }
+
+    /// Whether this is a valid token to send in a header.
+    ///
+    /// Only an `Encoding::Ext` can be invalid, such as when it is empty,
+    /// longer than `Encoding::MAX_LEN`, or contains spaces or control
+    /// characters.
+    pub(crate) fn is_token(&self) -> bool {
+        is_token(self.as_str())
+    }
+
+    /// Whether a `deflate` body is expected to be zlib-wrapped.
+    ///
+    /// [RFC7230](https://tools.ietf.org/html/rfc7230#section-4.2.2) defines
+    /// `deflate` as the zlib format, but some implementations historically
+    /// sent raw deflate data instead. Clients widely accept both, so the
+    /// interoperable choice when producing a `deflate` body is zlib, and
+    /// this always returns `true`.
+    pub const fn deflate_is_zlib() -> bool {
+        true
+    }
+
+    /// The file extension of files precompressed with this encoding, such
+    /// as `.gz` for `gzip`.
+    ///
+    /// A static file server can use this to look up a sibling file like
+    /// `index.html.gz`. Returns `None` for encodings that aren't applied to
+    /// files, like `chunked`, `identity` or `trailers`, and for an
+    /// `Encoding::Ext`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::Encoding;
+    ///
+    /// assert_eq!(Encoding::Gzip.file_extension(), Some(".gz"));
+    /// assert_eq!(Encoding::Identity.file_extension(), None);
+    /// ```
+    pub fn file_extension(&self) -> Option<&'static str> {
+        match *self {
+            Encoding::Brotli => Some(".br"),
+            Encoding::Gzip => Some(".gz"),
+            Encoding::Deflate => Some(".zz"),
+            Encoding::Compress => Some(".Z"),
+            Encoding::Zstd => Some(".zst"),
+            Encoding::Chunked | Encoding::Identity | Encoding::Trailers | Encoding::Ext(_) => None,
+        }
+    }
+
+    /// The name codec crates, such as `async-compression`, use for the
+    /// algorithm behind this encoding.
+    ///
+    /// Since `deflate` is zlib-wrapped (see
+    /// [`deflate_is_zlib`](Encoding::deflate_is_zlib)), it maps to `zlib`.
+    /// Returns `None` for encodings that aren't a compression codec, or that
+    /// those crates don't implement.
+    #[cfg(feature = "codec")]
+    pub fn codec_name(&self) -> Option<&'static str> {
+        match *self {
+            Encoding::Brotli => Some("brotli"),
+            Encoding::Gzip => Some("gzip"),
+            Encoding::Deflate => Some("zlib"),
+            Encoding::Zstd => Some("zstd"),
+            Encoding::Chunked
+            | Encoding::Compress
+            | Encoding::Identity
+            | Encoding::Trailers
+            | Encoding::Ext(_) => None,
+        }
+    }
+}
+
+/// Select the encoding out of `available` that `items` prefer.
+///
+/// This is the negotiation of
+/// [`AcceptEncoding::preferred_encoding`](crate::AcceptEncoding::preferred_encoding),
+/// for quality lists that didn't come from an `Accept-Encoding` header, such
+/// as the ones of `TE`. Unlike there, `identity` is not implicitly
+/// available, only `available` is considered.
+///
+/// The encoding with the highest quality wins, and ties are broken by the
+/// order of `available`. A `*` item matches any encoding not listed
+/// explicitly, and encodings with `q=0` are never selected.
+///
+/// # Example
+///
+/// ```
+/// use headers::{best_encoding, Encoding, QualityValue};
+///
+/// let items = ["gzip;q=0.5", "br"]
+///     .iter()
+///     .map(|s| s.parse::<QualityValue<Encoding>>().unwrap())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     best_encoding(&items, &[Encoding::Gzip, Encoding::Brotli]),
+///     Some(Encoding::Brotli),
+/// );
+/// ```
+pub fn best_encoding(items: &[QualityValue<Encoding>], available: &[Encoding]) -> Option<Encoding> {
+    negotiation::negotiate(items, available, available)
+}
+
+/// Fail if any of the coding `items` is `trailers`.
+///
+/// `trailers` is not a coding, but tells that the client accepts trailer
+/// fields, which is only meaningful in `TE`. `Accept-Encoding` and
+/// `Content-Encoding` reject it.
+pub(crate) fn reject_trailers<'a, I>(items: I) -> Result<(), crate::Error>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let has_trailers = items
+        .into_iter()
+        .any(|item| item.parse::<Encoding>().ok() == Some(Encoding::Trailers));
+    if has_trailers {
+        Err(crate::Error::invalid())
+    } else {
+        Ok(())
+    }
+}
+
+// Content-codings have no notion of specificity, so ties keep their order.
+impl Specificity for Encoding {}
+
+impl Wildcard for Encoding {
+    fn is_wildcard(&self) -> bool {
+        matches!(*self, Encoding::Ext(ref s) if s == negotiation::ANY_TOKEN)
+    }
+}
+
+/// Compares against the token case-insensitively, also accepting the
+/// `brotli` alias of `br`.
+///
+/// # Example
+///
+/// ```
+/// use headers::Encoding;
+///
+/// assert!(Encoding::Gzip == "GZIP");
+/// assert!(Encoding::Brotli == "brotli");
+/// ```
+impl PartialEq<str> for Encoding {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str().eq_ignore_ascii_case(other)
+            || (*self == Encoding::Brotli && other.eq_ignore_ascii_case("brotli"))
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Encoding {
+    fn eq(&self, other: &&'a str) -> bool {
+        *self == **other
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<'a> TryFrom<&'a HeaderValue> for Encoding {
+    type Error = crate::Error;
+
+    fn try_from(value: &'a HeaderValue) -> Result<Encoding, crate::Error> {
+        value.to_str().map_err(|_| crate::Error::invalid())?.parse()
+    }
+}
+
+/// # Panics
+///
+/// Panics if an `Encoding::Ext` built by hand contains bytes not allowed in
+/// a header value. This can't happen for parsed encodings, which are always
+/// tokens.
+impl From<Encoding> for HeaderValue {
+    fn from(encoding: Encoding) -> HeaderValue {
+        match encoding {
+            Encoding::Chunked => HeaderValue::from_static("chunked"),
+            Encoding::Brotli => HeaderValue::from_static("br"),
+            Encoding::Gzip => HeaderValue::from_static("gzip"),
+            Encoding::Deflate => HeaderValue::from_static("deflate"),
+            Encoding::Compress => HeaderValue::from_static("compress"),
+            Encoding::Zstd => HeaderValue::from_static("zstd"),
+            Encoding::Identity => HeaderValue::from_static("identity"),
+            Encoding::Trailers => HeaderValue::from_static("trailers"),
+            Encoding::Ext(s) => {
+                HeaderValue::try_from(s).expect("Encoding::Ext is a valid HeaderValue")
+            }
+        }
+    }
+}
+
+impl str::FromStr for Encoding {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Encoding, crate::Error> {
+        // Parameters are not part of the coding name, so `gzip;q=1` or
+        // `gzip;level=9` are still just `gzip`.
+        let s = s.split(';').next().unwrap_or("").trim();
+        if !is_token(s) {
+            return Err(crate::Error::invalid());
+        }
+
+        // Some clients spell out `brotli` instead of sending `br`.
+        if s.eq_ignore_ascii_case("brotli") {
+            return Ok(Encoding::Brotli);
+        }
+
+        Ok(WELL_KNOWN
+            .iter()
+            .find(|enc| enc.as_str().eq_ignore_ascii_case(s))
+            .cloned()
+            .unwrap_or_else(|| Encoding::Ext(s.to_owned())))
+    }
+}
+
+/// Whether `s` is a `token` of at most `Encoding::MAX_LEN` bytes.
+fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.len() <= Encoding::MAX_LEN
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+#[cfg(test)]
+impl proptest::arbitrary::Arbitrary for Encoding {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Encoding>;
+
+    /// Any well known encoding, or an `x-` prefixed extension, which is never
+    /// parsed as a well known one.
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        prop_oneof![
+            proptest::sample::select(Encoding::well_known()),
+            "x-[a-z0-9]{1,12}".prop_map(Encoding::Ext),
+            Just(Encoding::star()),
+        ]
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use http::HeaderValue;
+
+    use super::{best_encoding, Encoding};
+    use crate::util::{q, QualityValue};
+
+    #[test]
+    fn identity_and_star() {
+        assert_eq!(Encoding::IDENTITY, Encoding::Identity);
+        assert_eq!("identity".parse::<Encoding>().unwrap(), Encoding::IDENTITY);
+        assert_eq!("*".parse::<Encoding>().unwrap(), Encoding::star());
+        assert_eq!(Encoding::star().to_string(), "*");
+        assert!(!Encoding::Ext("x-star".into()).is_star());
+    }
+
+    #[test]
+    fn file_extension() {
+        assert_eq!(Encoding::Gzip.file_extension(), Some(".gz"));
+        assert_eq!(Encoding::Brotli.file_extension(), Some(".br"));
+        assert_eq!(Encoding::Zstd.file_extension(), Some(".zst"));
+        assert_eq!(Encoding::Identity.file_extension(), None);
+        assert_eq!(Encoding::Chunked.file_extension(), None);
+        assert_eq!(Encoding::Trailers.file_extension(), None);
+        assert_eq!(Encoding::Ext("x-foo".into()).file_extension(), None);
+    }
+
+    #[test]
+    fn eq_str() {
+        assert!(Encoding::Gzip == "GZIP");
+        assert!(Encoding::Gzip == *"gzip");
+        assert!(Encoding::Brotli == "br");
+        assert!(Encoding::Brotli == "Brotli");
+        assert!(Encoding::Ext("x-foo".into()) == "x-foo");
+        assert!(Encoding::Ext("x-foo".into()) == "X-Foo");
+
+        assert!(Encoding::Gzip != "deflate");
+        assert!(Encoding::Gzip != "gzip;q=1");
+        assert!(Encoding::Ext("brotli".into()) != "br");
+    }
+
+    #[test]
+    fn from_str_empty() {
+        assert!("".parse::<Encoding>().is_err());
+        assert!(" \t".parse::<Encoding>().is_err());
+        assert!(";q=0.5".parse::<Encoding>().is_err());
+    }
+
+    #[test]
+    fn from_str_known() {
+        assert_eq!("gzip".parse::<Encoding>().unwrap(), Encoding::Gzip);
+        assert_eq!("br".parse::<Encoding>().unwrap(), Encoding::Brotli);
+        assert_eq!("zstd".parse::<Encoding>().unwrap(), Encoding::Zstd);
+        assert_eq!("identity".parse::<Encoding>().unwrap(), Encoding::Identity);
+    }
+
+    #[test]
+    fn well_known() {
+        let known = Encoding::well_known();
+        for encoding in &[
+            Encoding::Gzip,
+            Encoding::Brotli,
+            Encoding::Deflate,
+            Encoding::Identity,
+        ] {
+            assert!(known.contains(encoding), "{:?}", encoding);
+        }
+
+        for encoding in known {
+            assert!(!matches!(*encoding, Encoding::Ext(_)));
+            assert_eq!(encoding.as_str().parse::<Encoding>().unwrap(), *encoding);
+        }
+    }
+
+    #[test]
+    fn from_str_case_insensitive() {
+        assert_eq!("GZip".parse::<Encoding>().unwrap(), Encoding::Gzip);
+    }
+
+    #[test]
+    fn from_str_brotli_alias() {
+        let enc = "Brotli".parse::<Encoding>().unwrap();
+        assert_eq!(enc, Encoding::Brotli);
+        assert_eq!(enc.to_string(), "br");
+    }
+
+    #[test]
+    fn from_str_ext() {
+        assert_eq!(
+            "x-custom".parse::<Encoding>().unwrap(),
+            Encoding::Ext("x-custom".into())
+        );
+    }
+
+    #[test]
+    fn deflate_is_zlib() {
+        assert!(Encoding::deflate_is_zlib());
+    }
+
+    #[cfg(feature = "codec")]
+    #[test]
+    fn codec_name() {
+        assert_eq!(Encoding::Gzip.codec_name(), Some("gzip"));
+        assert_eq!(Encoding::Brotli.codec_name(), Some("brotli"));
+        assert_eq!(Encoding::Deflate.codec_name(), Some("zlib"));
+        assert_eq!(Encoding::Zstd.codec_name(), Some("zstd"));
+        assert_eq!(Encoding::Identity.codec_name(), None);
+        assert_eq!(Encoding::Ext("x-custom".into()).codec_name(), None);
+    }
+
+    #[test]
+    fn from_str_too_long() {
+        let max = "x".repeat(Encoding::MAX_LEN);
+        assert_eq!(max.parse::<Encoding>().unwrap(), Encoding::Ext(max.clone()));
+
+        let too_long = "x".repeat(Encoding::MAX_LEN + 1);
+        assert!(too_long.parse::<Encoding>().is_err());
+    }
+
+    #[test]
+    fn from_str_ignores_params() {
+        assert_eq!("gzip;level=9".parse::<Encoding>().unwrap(), Encoding::Gzip);
+        assert_eq!(
+            "x-custom ; a=b".parse::<Encoding>().unwrap(),
+            Encoding::Ext("x-custom".into())
+        );
+    }
+
+    #[test]
+    fn header_value_roundtrip() {
+        let encodings = [
+            Encoding::Chunked,
+            Encoding::Brotli,
+            Encoding::Gzip,
+            Encoding::Deflate,
+            Encoding::Compress,
+            Encoding::Zstd,
+            Encoding::Identity,
+            Encoding::Trailers,
+            Encoding::Ext("x-custom".into()),
+        ];
+
+        for encoding in &encodings {
+            let value = HeaderValue::from(encoding.clone());
+            assert_eq!(value, encoding.as_str());
+            assert_eq!(Encoding::try_from(&value).unwrap(), *encoding);
+        }
+    }
+
+    #[test]
+    fn from_str_rejects_non_tokens() {
+        assert!("a\u{7f}".parse::<Encoding>().is_err());
+        assert!("x custom".parse::<Encoding>().is_err());
+        assert!("(gzip)".parse::<Encoding>().is_err());
+        assert!("x-custom".parse::<Encoding>().is_ok());
+    }
+
+    #[test]
+    fn try_from_header_value_invalid() {
+        let value = HeaderValue::from_bytes(b"gzip\xff").unwrap();
+        assert!(Encoding::try_from(&value).is_err());
+    }
+
+    #[test]
+    fn best_encoding_hand_built() {
+        let items = [
+            QualityValue::new(Encoding::Gzip, q(800)),
+            QualityValue::new(Encoding::Brotli, q(0)),
+            QualityValue::new(Encoding::star(), q(300)),
+        ];
+
+        assert_eq!(
+            best_encoding(&items, &[Encoding::Brotli, Encoding::Gzip]),
+            Some(Encoding::Gzip)
+        );
+        assert_eq!(
+            best_encoding(&items, &[Encoding::Brotli, Encoding::Zstd]),
+            Some(Encoding::Zstd)
+        );
+        assert_eq!(best_encoding(&items, &[Encoding::Brotli]), None);
+        assert_eq!(best_encoding(&[], &[Encoding::Gzip]), None);
+    }
+}
@@ -9,6 +9,11 @@ use crate::util::TryFromValues;
 use crate::Error;
 
 // A single `HeaderValue` that can flatten multiple values with commas.
+//
+// Obsolete line folding (obs-fold, RFC7230 section 3.2.4) can't reach this:
+// a `HeaderValue` can't contain CR or LF, so a parser has to either reject
+// a folded value, or replace each obs-fold with spaces first. The whitespace
+// that replacement leaves around items is trimmed like any other.
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub(crate) struct FlatCsv<Sep = Comma> {
     pub(crate) value: HeaderValue,
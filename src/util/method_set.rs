@@ -0,0 +1,168 @@
+use std::iter::FromIterator;
+
+use http::{HeaderValue, Method};
+
+use crate::util::{FlatCsv, TryFromValues};
+use crate::Error;
+
+/// A list of methods, backing `Allow` and `Access-Control-Allow-Methods`.
+///
+/// Standard methods are parsed case-insensitively, and every method is only
+/// kept once, in the order it was first seen. Items that aren't methods are
+/// skipped.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct MethodSet(Vec<Method>);
+
+impl MethodSet {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Method> {
+        self.0.iter()
+    }
+
+    pub(crate) fn contains(&self, method: &Method) -> bool {
+        self.0.contains(method)
+    }
+
+    /// Adds `method`, returning whether it wasn't in the set yet.
+    pub(crate) fn insert(&mut self, method: Method) -> bool {
+        if self.contains(&method) {
+            false
+        } else {
+            self.0.push(method);
+            true
+        }
+    }
+}
+
+impl FromIterator<Method> for MethodSet {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Method>,
+    {
+        let mut set = MethodSet::default();
+        for method in iter {
+            set.insert(method);
+        }
+        set
+    }
+}
+
+impl TryFromValues for MethodSet {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let flat: FlatCsv = values.collect();
+        Ok(flat.iter().filter_map(parse_method).collect())
+    }
+}
+
+impl<'a> From<&'a MethodSet> for HeaderValue {
+    fn from(set: &'a MethodSet) -> HeaderValue {
+        match set.0.as_slice() {
+            [method] => to_value(method),
+            methods => {
+                let s = methods
+                    .iter()
+                    .map(Method::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                HeaderValue::from_str(&s).expect("Methods are also valid HeaderValues")
+            }
+        }
+    }
+}
+
+/// Parse a single method, matching the standard methods case-insensitively.
+///
+/// Extension methods are case-sensitive, and are kept as written.
+pub(crate) fn parse_method(s: &str) -> Option<Method> {
+    let standard = match s.to_ascii_uppercase().as_str() {
+        "GET" => Method::GET,
+        "HEAD" => Method::HEAD,
+        "POST" => Method::POST,
+        "PUT" => Method::PUT,
+        "DELETE" => Method::DELETE,
+        "CONNECT" => Method::CONNECT,
+        "OPTIONS" => Method::OPTIONS,
+        "TRACE" => Method::TRACE,
+        "PATCH" => Method::PATCH,
+        _ => return Method::from_bytes(s.as_bytes()).ok(),
+    };
+    Some(standard)
+}
+
+pub(crate) fn to_value(method: &Method) -> HeaderValue {
+    // For the more common methods, try to use a static string.
+    let s = match *method {
+        Method::GET => "GET",
+        Method::POST => "POST",
+        Method::PUT => "PUT",
+        Method::DELETE => "DELETE",
+        _ => {
+            return HeaderValue::from_str(method.as_ref())
+                .expect("Methods are also valid HeaderValues")
+        }
+    };
+    HeaderValue::from_static(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(values: &[&'static str]) -> MethodSet {
+        let values = values
+            .iter()
+            .map(|&s| HeaderValue::from_static(s))
+            .collect::<Vec<_>>();
+        MethodSet::try_from_values(&mut values.iter()).unwrap()
+    }
+
+    #[test]
+    fn parse_method_case_insensitive() {
+        assert_eq!(parse_method("GET"), Some(Method::GET));
+        assert_eq!(parse_method("get"), Some(Method::GET));
+        assert_eq!(parse_method("Patch"), Some(Method::PATCH));
+        assert_eq!(parse_method("fOObAr").unwrap().as_str(), "fOObAr");
+        assert_eq!(parse_method(""), None);
+        assert_eq!(parse_method("GET POST"), None);
+    }
+
+    #[test]
+    fn dedup() {
+        let set = decode(&["GET, get, PUT", "Get, fOObAr, fOObAr, FOOBAR"]);
+        assert_eq!(
+            set.iter().map(Method::as_str).collect::<Vec<_>>(),
+            ["GET", "PUT", "fOObAr", "FOOBAR"]
+        );
+        assert!(set.contains(&Method::PUT));
+        assert!(!set.contains(&Method::POST));
+    }
+
+    #[test]
+    fn skips_invalid() {
+        let set = decode(&["GET, , (POST), DELETE"]);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [&Method::GET, &Method::DELETE]
+        );
+    }
+
+    #[test]
+    fn insert() {
+        let mut set = MethodSet::default();
+        assert!(set.insert(Method::GET));
+        assert!(!set.insert(Method::GET));
+        assert!(set.insert(Method::HEAD));
+        assert_eq!(HeaderValue::from(&set), "GET, HEAD");
+    }
+
+    #[test]
+    fn to_header_value() {
+        assert_eq!(HeaderValue::from(&MethodSet::default()), "");
+        assert_eq!(
+            HeaderValue::from(&vec![Method::OPTIONS].into_iter().collect::<MethodSet>()),
+            "OPTIONS"
+        );
+    }
+}
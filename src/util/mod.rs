@@ -2,30 +2,6 @@ use http::HeaderValue;
 
 use crate::Error;
 
-//pub use self::charset::Charset;
-//pub use self::encoding::Encoding;
-pub(crate) use self::entity::{EntityTag, EntityTagRange};
-pub(crate) use self::flat_csv::{FlatCsv, SemiColon};
-pub(crate) use self::fmt::fmt;
-pub(crate) use self::http_date::HttpDate;
-pub(crate) use self::iter::IterExt;
-//pub use language_tags::LanguageTag;
-//pub use self::quality_value::{Quality, QualityValue};
-pub(crate) use self::seconds::Seconds;
-pub(crate) use self::value_string::HeaderValueString;
-
-//mod charset;
-pub(crate) mod csv;
-//mod encoding;
-mod entity;
-mod flat_csv;
-mod fmt;
-mod http_date;
-mod iter;
-//mod quality_value;
-mod seconds;
-mod value_string;
-
 macro_rules! error_type {
     ($name:ident) => {
         #[doc(hidden)]
@@ -70,6 +46,79 @@ macro_rules! derive_header {
     };
 }
 
+/// Convert a date header from and to `chrono` and `time` types, behind the
+/// features of the same name. The header must convert from and to a
+/// `SystemTime`.
+macro_rules! date_conversions {
+    ($type:ident) => {
+        #[cfg(feature = "chrono")]
+        impl<Tz: ::chrono::TimeZone> From<::chrono::DateTime<Tz>> for $type {
+            fn from(datetime: ::chrono::DateTime<Tz>) -> $type {
+                ::std::time::SystemTime::from(datetime).into()
+            }
+        }
+
+        #[cfg(feature = "chrono")]
+        impl From<$type> for ::chrono::DateTime<::chrono::Utc> {
+            fn from(date: $type) -> ::chrono::DateTime<::chrono::Utc> {
+                ::std::time::SystemTime::from(date).into()
+            }
+        }
+
+        #[cfg(feature = "time")]
+        impl From<::time::OffsetDateTime> for $type {
+            fn from(datetime: ::time::OffsetDateTime) -> $type {
+                ::std::time::SystemTime::from(datetime).into()
+            }
+        }
+
+        #[cfg(feature = "time")]
+        impl From<$type> for ::time::OffsetDateTime {
+            fn from(date: $type) -> ::time::OffsetDateTime {
+                ::std::time::SystemTime::from(date).into()
+            }
+        }
+    };
+}
+
+//pub use self::charset::Charset;
+pub use self::csv_list::CsvList;
+pub(crate) use self::encoding::reject_trailers;
+pub use self::encoding::{best_encoding, Encoding};
+pub(crate) use self::entity::{EntityTag, EntityTagRange};
+pub(crate) use self::flat_csv::{FlatCsv, SemiColon};
+pub(crate) use self::fmt::fmt;
+pub(crate) use self::http_date::HttpDate;
+pub(crate) use self::iter::IterExt;
+pub(crate) use self::method_set::MethodSet;
+pub(crate) use self::product::products;
+//pub use language_tags::LanguageTag;
+pub(crate) use self::quality_list::QualityList;
+#[cfg(test)]
+pub(crate) use self::quality_value::q;
+pub use self::quality_value::{InvalidQualityValue, Quality, QualityValue};
+pub(crate) use self::seconds::Seconds;
+pub(crate) use self::value_string::{trim_ows, HeaderValueString};
+
+//mod charset;
+pub(crate) mod csv;
+mod csv_list;
+mod encoding;
+mod entity;
+mod flat_csv;
+mod fmt;
+mod http_date;
+mod iter;
+pub(crate) mod method_set;
+pub(crate) mod negotiation;
+pub(crate) mod params;
+mod product;
+mod quality_list;
+mod quality_value;
+mod seconds;
+pub(crate) mod sf;
+mod value_string;
+
 /// A helper trait for use when deriving `Header`.
 pub(crate) trait TryFromValues: Sized {
     /// Try to convert from the values into an instance of `Self`.
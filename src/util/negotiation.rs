@@ -0,0 +1,323 @@
+//! Content negotiation shared by the `Accept-*` family of headers.
+//!
+//! All of them follow the same rules from
+//! [RFC7231](https://tools.ietf.org/html/rfc7231#section-5.3): the client
+//! sends a list of values weighted by quality, a wildcard matches anything not
+//! listed explicitly, and `q=0` means "not acceptable".
+//!
+//! Only `Accept-Encoding` is wired up so far; `Accept`, `Accept-Language` and
+//! `Accept-Charset` are still in `src/disabled`.
+
+use std::cmp::Ordering;
+
+use super::QualityValue;
+use crate::Quality;
+
+/// How specific a value in an `Accept-*` header is.
+///
+/// When two values have the same quality, the more specific one is
+/// preferred. For example `text/plain` is more specific than `text/*`,
+/// which is more specific than `*/*`, as described in
+/// [RFC7231](https://tools.ietf.org/html/rfc7231#section-5.3.2). When
+/// negotiating, a value that isn't listed itself gets the quality of the
+/// most specific range that matches it.
+///
+/// The default implementation treats all values as equally specific, so
+/// ties keep the order they were sent in.
+pub(crate) trait Specificity {
+    /// The specificity of this value, where higher is more specific.
+    fn specificity(&self) -> u8 {
+        0
+    }
+}
+
+/// `*/*` is 0, `type/*` is 1, `type/subtype` is 2, and 3 with parameters.
+impl Specificity for mime::Mime {
+    fn specificity(&self) -> u8 {
+        if self.type_() == mime::STAR {
+            0
+        } else if self.subtype() == mime::STAR {
+            1
+        } else if self.params().next().is_none() {
+            2
+        } else {
+            3
+        }
+    }
+}
+
+/// Order by preference: highest quality first, then most specific first.
+pub(crate) fn cmp_preference<T: Specificity>(a: &QualityValue<T>, b: &QualityValue<T>) -> Ordering {
+    b.quality()
+        .cmp(&a.quality())
+        .then_with(|| b.value().specificity().cmp(&a.value().specificity()))
+}
+
+/// Sort by preference, keeping the sent order of otherwise equal items.
+pub(crate) fn sort_by_preference<T: Specificity>(items: &mut [QualityValue<T>]) {
+    items.sort_by(cmp_preference);
+}
+
+/// The wildcard of `Accept-Encoding`, `Accept-Language` and `Accept-Charset`.
+///
+/// These list plain tokens, so a single `*` matches any token not listed.
+pub(crate) const ANY_TOKEN: &str = "*";
+
+/// The wildcard of `Accept`, matching any media type not listed.
+///
+/// A media type always has a type and a subtype, so a bare `*` isn't a
+/// valid `Accept` value. `type/*` only matches part of the media types,
+/// and isn't a full wildcard.
+pub(crate) const ANY_MEDIA_TYPE: &str = "*/*";
+
+/// A value that can be a wildcard in an `Accept-*` header.
+pub(crate) trait Wildcard {
+    /// Whether this value matches anything not explicitly listed.
+    fn is_wildcard(&self) -> bool;
+
+    /// Whether this listed value covers `value`, other than by being equal
+    /// to it. By default only a wildcard does.
+    fn matches(&self, value: &Self) -> bool {
+        let _ = value;
+        self.is_wildcard()
+    }
+}
+
+/// `*/*` is the only wildcard, but `text/*` matches any `text` media type,
+/// and `text/plain` matches it with any parameters.
+impl Wildcard for mime::Mime {
+    fn is_wildcard(&self) -> bool {
+        self.essence_str() == ANY_MEDIA_TYPE
+    }
+
+    fn matches(&self, value: &mime::Mime) -> bool {
+        if self.is_wildcard() {
+            return true;
+        }
+        if self.type_() != value.type_() {
+            return false;
+        }
+        if self.subtype() == mime::STAR {
+            return true;
+        }
+        self.subtype() == value.subtype()
+            && self
+                .params()
+                .all(|(name, param)| value.get_param(name) == Some(param))
+    }
+}
+
+/// The quality the client assigned to `value`.
+///
+/// An explicit entry takes precedence over the entries that
+/// [match](Wildcard::matches) it, out of which the most
+/// [specific](Specificity) one counts. If several entries are equally
+/// good, the highest quality wins. Returns `None` if no entry matches.
+pub(crate) fn quality_of<T>(client: &[QualityValue<T>], value: &T) -> Option<Quality>
+where
+    T: PartialEq + Specificity + Wildcard,
+{
+    let explicit = client
+        .iter()
+        .filter(|item| item.value() == value)
+        .map(QualityValue::quality)
+        .max();
+
+    explicit.or_else(|| {
+        client
+            .iter()
+            .filter(|item| item.value().matches(value))
+            .map(|item| (item.value().specificity(), item.quality()))
+            .max()
+            .map(|(_, quality)| quality)
+    })
+}
+
+/// Pick the value out of `available` that the client prefers.
+///
+/// The value with the highest client quality wins. Ties are broken by the
+/// position in `server_priority`, where values not listed there rank last,
+/// and then by the order of `available`. Values the client didn't list, or
+/// rejected with `q=0`, are never picked.
+pub(crate) fn negotiate<T>(
+    client: &[QualityValue<T>],
+    available: &[T],
+    server_priority: &[T],
+) -> Option<T>
+where
+    T: PartialEq + Clone + Specificity + Wildcard,
+{
+    let rank = |value: &T| {
+        server_priority
+            .iter()
+            .position(|v| v == value)
+            .unwrap_or(server_priority.len())
+    };
+
+    let mut best: Option<(Quality, usize, &T)> = None;
+    for value in available {
+        let quality = match quality_of(client, value) {
+            Some(quality) if !quality.is_zero() => quality,
+            _ => continue,
+        };
+        let rank = rank(value);
+
+        let better = match best {
+            None => true,
+            Some((best_quality, best_rank, _)) => {
+                quality > best_quality || (quality == best_quality && rank < best_rank)
+            }
+        };
+        if better {
+            best = Some((quality, rank, value));
+        }
+    }
+    best.map(|(_, _, value)| value.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::q;
+
+    impl Specificity for &'static str {}
+
+    impl Wildcard for &'static str {
+        fn is_wildcard(&self) -> bool {
+            *self == ANY_TOKEN
+        }
+    }
+
+    fn client(items: &[(&'static str, u16)]) -> Vec<QualityValue<&'static str>> {
+        items
+            .iter()
+            .map(|&(value, quality)| QualityValue::new(value, q(quality)))
+            .collect()
+    }
+
+    #[test]
+    fn highest_quality_wins() {
+        let client = client(&[("a", 500), ("b", 1000)]);
+        assert_eq!(negotiate(&client, &["a", "b"], &[]), Some("b"));
+    }
+
+    #[test]
+    fn tie_uses_server_priority() {
+        let client = client(&[("a", 1000), ("b", 1000)]);
+        assert_eq!(negotiate(&client, &["a", "b"], &["b", "a"]), Some("b"));
+        assert_eq!(negotiate(&client, &["a", "b"], &["a"]), Some("a"));
+    }
+
+    #[test]
+    fn tie_without_priority_uses_available_order() {
+        let client = client(&[("a", 1000), ("b", 1000)]);
+        assert_eq!(negotiate(&client, &["b", "a"], &[]), Some("b"));
+    }
+
+    #[test]
+    fn wildcard_matches_unlisted() {
+        let client = client(&[("a", 0), ("*", 300)]);
+        assert_eq!(negotiate(&client, &["a", "c"], &[]), Some("c"));
+        assert_eq!(quality_of(&client, &"c"), Some(q(300)));
+        assert_eq!(quality_of(&client, &"a"), Some(q(0)));
+    }
+
+    #[test]
+    fn rejected_and_unlisted_are_never_picked() {
+        let client = client(&[("a", 0), ("b", 1000)]);
+        assert_eq!(negotiate(&client, &["a", "c"], &[]), None);
+        assert_eq!(quality_of(&client, &"c"), None);
+    }
+
+    #[test]
+    fn duplicates_use_highest_quality() {
+        let client = client(&[("a", 200), ("a", 900), ("b", 500)]);
+        assert_eq!(negotiate(&client, &["b", "a"], &[]), Some("a"));
+    }
+
+    fn mimes(items: &[&str]) -> Vec<QualityValue<mime::Mime>> {
+        let mut items = items
+            .iter()
+            .map(|s| s.parse::<QualityValue<mime::Mime>>().unwrap())
+            .collect::<Vec<_>>();
+        sort_by_preference(&mut items);
+        items
+    }
+
+    fn values(items: &[QualityValue<mime::Mime>]) -> Vec<String> {
+        items.iter().map(|item| item.value().to_string()).collect()
+    }
+
+    #[test]
+    fn accept_specificity_breaks_ties() {
+        let items = mimes(&["*/*", "text/*", "text/plain", "text/plain;format=flowed"]);
+
+        assert_eq!(
+            values(&items),
+            vec!["text/plain;format=flowed", "text/plain", "text/*", "*/*"]
+        );
+    }
+
+    #[test]
+    fn accept_quality_beats_specificity() {
+        let items = mimes(&["text/plain;q=0.5", "*/*;q=0.1", "text/*"]);
+
+        assert_eq!(values(&items), vec!["text/*", "text/plain", "*/*"]);
+    }
+
+    #[test]
+    fn default_specificity_keeps_order() {
+        let mut items = client(&[("b", 500), ("*", 1000), ("a", 1000)]);
+        sort_by_preference(&mut items);
+
+        let values = items.iter().map(|item| *item.value()).collect::<Vec<_>>();
+        assert_eq!(values, vec!["*", "a", "b"]);
+    }
+
+    #[test]
+    fn accept_any_media_type() {
+        let client = mimes(&["text/plain;q=0.5", "*/*;q=0.1"]);
+        let json = mime::APPLICATION_JSON;
+
+        assert!(ANY_MEDIA_TYPE.parse::<mime::Mime>().unwrap().is_wildcard());
+        assert!(!mime::TEXT_STAR.is_wildcard());
+        assert_eq!(quality_of(&client, &json), Some(q(100)));
+        assert_eq!(quality_of(&client, &mime::TEXT_PLAIN), Some(q(500)));
+        assert_eq!(
+            negotiate(&client, &[json.clone(), mime::TEXT_PLAIN], &[]),
+            Some(mime::TEXT_PLAIN)
+        );
+
+        // `text/*` doesn't match outside of `text`.
+        let client = mimes(&["text/*"]);
+        assert_eq!(quality_of(&client, &json), None);
+    }
+
+    #[test]
+    fn accept_most_specific_range_counts() {
+        let mime = |s: &str| s.parse::<mime::Mime>().unwrap();
+        let client = mimes(&[
+            "*/*;q=0.5",
+            "text/*;q=0.1",
+            "text/html",
+            "text/html;level=1;q=0.3",
+        ]);
+
+        assert_eq!(quality_of(&client, &mime::TEXT_HTML), Some(q(1000)));
+        assert_eq!(
+            quality_of(&client, &mime("text/html;level=1")),
+            Some(q(300))
+        );
+        assert_eq!(quality_of(&client, &mime::TEXT_PLAIN), Some(q(100)));
+        assert_eq!(quality_of(&client, &mime::IMAGE_PNG), Some(q(500)));
+
+        assert_eq!(
+            negotiate(&client, &[mime::TEXT_PLAIN, mime::IMAGE_PNG], &[]),
+            Some(mime::IMAGE_PNG)
+        );
+        assert_eq!(
+            negotiate(&client, &[mime::TEXT_PLAIN, mime::TEXT_HTML], &[]),
+            Some(mime::TEXT_HTML)
+        );
+    }
+}
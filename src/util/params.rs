@@ -0,0 +1,111 @@
+//! Generic `key=value` parameter lists, such as `Keep-Alive: timeout=5,
+//! max=100`, or the parameters of a `Link` value.
+
+use std::collections::HashMap;
+
+/// Parse a list of parameters separated by `,` or `;`.
+///
+/// Keys are lowercased, and a key without `=` has no value. Quoted values
+/// are unescaped, and separators inside of them are kept. If a key is
+/// listed more than once, the last value wins.
+pub(crate) fn parse(s: &str) -> HashMap<String, Option<String>> {
+    let mut params = HashMap::new();
+    for item in split(s) {
+        let (key, value) = match item.split_once('=') {
+            Some((key, value)) => (key.trim(), Some(unquote(value.trim()))),
+            None => (item, None),
+        };
+        if !key.is_empty() {
+            params.insert(key.to_ascii_lowercase(), value);
+        }
+    }
+    params
+}
+
+/// Split on `,` and `;` outside of quoted strings, trimming every item and
+/// skipping empty ones.
+fn split(s: &str) -> impl Iterator<Item = &str> {
+    let mut in_quotes = false;
+    let mut escaped = false;
+    s.split(move |c| {
+        if escaped {
+            escaped = false;
+        } else if in_quotes {
+            match c {
+                '\\' => escaped = true,
+                '"' => in_quotes = false,
+                _ => (),
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else {
+            return c == ',' || c == ';';
+        }
+        false
+    })
+    .map(str::trim)
+    .filter(|item| !item.is_empty())
+}
+
+fn unquote(value: &str) -> String {
+    if value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+        return value.to_owned();
+    }
+
+    let mut unquoted = String::with_capacity(value.len() - 2);
+    let mut chars = value[1..value.len() - 1].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unquoted.extend(chars.next()),
+            c => unquoted.push(c),
+        }
+    }
+    unquoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get<'a>(params: &'a HashMap<String, Option<String>>, key: &str) -> Option<&'a str> {
+        params.get(key)?.as_deref()
+    }
+
+    #[test]
+    fn unquoted() {
+        let params = parse("timeout=5, max=100");
+        assert_eq!(params.len(), 2);
+        assert_eq!(get(&params, "timeout"), Some("5"));
+        assert_eq!(get(&params, "max"), Some("100"));
+    }
+
+    #[test]
+    fn semicolons_and_flags() {
+        let params = parse("rel=next; Type=text/html; crossorigin");
+        assert_eq!(get(&params, "rel"), Some("next"));
+        assert_eq!(get(&params, "type"), Some("text/html"));
+        assert_eq!(params.get("crossorigin"), Some(&None));
+    }
+
+    #[test]
+    fn quoted() {
+        let params = parse(r#"title="a, \"b\"; c", rel = "next""#);
+        assert_eq!(params.len(), 2);
+        assert_eq!(get(&params, "title"), Some(r#"a, "b"; c"#));
+        assert_eq!(get(&params, "rel"), Some("next"));
+    }
+
+    #[test]
+    fn duplicate_keys_last_wins() {
+        let params = parse("max=1, timeout=2, MAX=3");
+        assert_eq!(get(&params, "max"), Some("3"));
+        assert_eq!(get(&params, "timeout"), Some("2"));
+    }
+
+    #[test]
+    fn empty_items() {
+        let params = parse(" , ;=1; max=2,");
+        assert_eq!(params.len(), 1);
+        assert_eq!(get(&params, "max"), Some("2"));
+    }
+}
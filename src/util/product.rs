@@ -0,0 +1,105 @@
+/// An iterator over the products of a `Server` or `User-Agent` value.
+///
+/// ```text
+/// product         = token ["/" product-version]
+/// product-version = token
+/// comment         = "(" *( ctext / quoted-pair / comment ) ")"
+/// ```
+///
+/// Comments between the products are skipped.
+pub(crate) struct Products<'a> {
+    rest: &'a str,
+}
+
+pub(crate) fn products(s: &str) -> Products<'_> {
+    Products { rest: s }
+}
+
+impl<'a> Iterator for Products<'a> {
+    type Item = (&'a str, Option<&'a str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.rest = self.rest.trim_start_matches(|c| c == ' ' || c == '\t');
+            if self.rest.starts_with('(') {
+                self.rest = skip_comment(self.rest);
+                continue;
+            }
+            if self.rest.is_empty() {
+                return None;
+            }
+
+            let end = self
+                .rest
+                .find(|c| c == ' ' || c == '\t' || c == '(')
+                .unwrap_or(self.rest.len());
+            let (product, rest) = self.rest.split_at(end);
+            self.rest = rest;
+
+            return Some(match product.split_once('/') {
+                Some((name, version)) => (name, Some(version)),
+                None => (product, None),
+            });
+        }
+    }
+}
+
+/// Skip the comment at the start of `s`, which may be nested. An unclosed
+/// comment runs to the end.
+fn skip_comment(s: &str) -> &str {
+    let mut depth = 0usize;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &s[i + 1..];
+                }
+            }
+            _ => (),
+        }
+    }
+    ""
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn products_and_comments() {
+        assert_eq!(
+            products("nginx/1.25.3 (Ubuntu)").collect::<Vec<_>>(),
+            [("nginx", Some("1.25.3"))]
+        );
+        assert_eq!(
+            products("Mozilla/5.0 (X11; Linux x86_64) Gecko/20100101 Firefox/118.0")
+                .collect::<Vec<_>>(),
+            [
+                ("Mozilla", Some("5.0")),
+                ("Gecko", Some("20100101")),
+                ("Firefox", Some("118.0")),
+            ]
+        );
+    }
+
+    #[test]
+    fn without_version() {
+        assert_eq!(products("Bunnies").collect::<Vec<_>>(), [("Bunnies", None)]);
+    }
+
+    #[test]
+    fn nested_and_escaped_comments() {
+        assert_eq!(
+            products("a/1 (x (y\\) z) w)b/2 (unclosed c/3").collect::<Vec<_>>(),
+            [("a", Some("1")), ("b", Some("2"))]
+        );
+    }
+}
@@ -0,0 +1,409 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use http::HeaderValue;
+
+use super::negotiation::{self, Specificity, Wildcard};
+use super::{FlatCsv, QualityValue, TryFromValues};
+use crate::{Error, Quality};
+
+/// A comma-separated list of quality items, backing the `Accept-*` family
+/// of headers and `TE`.
+///
+/// The raw value is kept, so encoding writes back exactly what was
+/// received. Items are parsed when they are looked at, and items that can't
+/// be parsed as a `QualityValue<T>` are skipped, but kept in the value.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) struct QualityList<T> {
+    flat: FlatCsv,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> QualityList<T> {
+    /// The raw items, trimmed, without empty ones.
+    pub(crate) fn raw_items(&self) -> impl Iterator<Item = &str> {
+        self.flat.iter().filter(|s| !s.is_empty())
+    }
+
+    /// Whether there are no items, as in `Accept-Encoding: `.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.raw_items().next().is_none()
+    }
+
+    pub(crate) fn as_raw_str(&self) -> Option<&str> {
+        self.flat.value.to_str().ok()
+    }
+
+    /// Append `value` verbatim, without parsing it.
+    pub(crate) fn append_raw(&mut self, value: &HeaderValue) {
+        if self.is_empty() {
+            self.flat = value.clone().into();
+        } else {
+            self.flat = [&self.flat.value, value].iter().copied().collect();
+        }
+    }
+
+    /// Replace the items, writing every item with a valid weight the way
+    /// `QualityValue` displays it, as in `gzip; q=0.5`, so a rewritten value
+    /// doesn't mix formats. Parameters are kept, and items with an invalid
+    /// weight are written back as they were.
+    fn set_raw_items(&mut self, items: Vec<String>) {
+        let items = items
+            .iter()
+            .map(|raw| match raw.parse::<QualityValue<String>>() {
+                Ok(item) => item.to_string(),
+                Err(_) => raw.clone(),
+            })
+            .collect::<Vec<_>>();
+        // Every item came out of a valid value, or was checked by `insert`.
+        if let Ok(value) = items.join(", ").parse::<HeaderValue>() {
+            self.flat = value.into();
+        }
+    }
+}
+
+impl<T: FromStr> QualityList<T> {
+    /// The items that can be parsed, in the order they were sent.
+    fn parsed(&self) -> impl Iterator<Item = QualityValue<T>> + '_ {
+        self.raw_items()
+            .filter_map(|s| s.parse::<QualityValue<T>>().ok())
+    }
+
+    /// The items sorted by preference, where items of equal preference keep
+    /// the order they were sent in.
+    pub(crate) fn to_vec(&self) -> Vec<QualityValue<T>>
+    where
+        T: Specificity,
+    {
+        let mut items = self.parsed().collect::<Vec<_>>();
+        negotiation::sort_by_preference(&mut items);
+        items
+    }
+
+    /// The quality the client assigned to `value`, falling back to the
+    /// wildcard.
+    pub(crate) fn quality_of(&self, value: &T) -> Option<Quality>
+    where
+        T: PartialEq + Specificity + Wildcard,
+    {
+        negotiation::quality_of(&self.parsed().collect::<Vec<_>>(), value)
+    }
+
+    /// Select the value out of `available` that the client prefers, see
+    /// `negotiation::negotiate`.
+    pub(crate) fn negotiate(&self, available: &[T], server_priority: &[T]) -> Option<T>
+    where
+        T: PartialEq + Clone + Specificity + Wildcard,
+    {
+        negotiation::negotiate(
+            &self.parsed().collect::<Vec<_>>(),
+            available,
+            server_priority,
+        )
+    }
+
+    /// The quality of every listed value. If a value is listed several
+    /// times, the highest quality counts.
+    pub(crate) fn quality_map(&self) -> HashMap<T, Quality>
+    where
+        T: Eq + Hash,
+    {
+        let mut map = HashMap::new();
+        for item in self.parsed() {
+            let quality = item.quality();
+            let entry = map.entry(item.into_value()).or_insert(quality);
+            if quality > *entry {
+                *entry = quality;
+            }
+        }
+        map
+    }
+
+    /// Set the quality of `value`, replacing the first entry for it in
+    /// place, and dropping any others. Otherwise, the value is appended.
+    ///
+    /// Nothing changes if `value` can't be written to a header value.
+    pub(crate) fn insert(&mut self, value: T, quality: Quality)
+    where
+        T: PartialEq + fmt::Display,
+    {
+        let new = QualityValue::new(value, quality);
+        if HeaderValue::from_str(&new.to_string()).is_err() {
+            return;
+        }
+        let mut replaced = false;
+        let mut items = Vec::new();
+        for raw in self.raw_items() {
+            if !mentions(raw, new.value()) {
+                items.push(raw.to_owned());
+            } else if !replaced {
+                items.push(new.to_string());
+                replaced = true;
+            }
+        }
+        if !replaced {
+            items.push(new.to_string());
+        }
+        self.set_raw_items(items);
+    }
+
+    /// Remove every entry for `value`, returning whether any was removed.
+    pub(crate) fn remove(&mut self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let before = self.raw_items().count();
+        let items = self
+            .raw_items()
+            .filter(|raw| !mentions(raw, value))
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+        let removed = items.len() != before;
+        self.set_raw_items(items);
+        removed
+    }
+
+    /// Collapse repeated entries for the same value into the one with the
+    /// highest quality, or the first one if several share it, at the
+    /// position of the first entry. Entries that can't be parsed are kept.
+    pub(crate) fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        let raws = self.raw_items().collect::<Vec<_>>();
+        let parsed = raws
+            .iter()
+            .map(|raw| raw.parse::<QualityValue<T>>().ok())
+            .collect::<Vec<_>>();
+
+        let best = |value: &T| {
+            let mut best: Option<(usize, Quality)> = None;
+            for (idx, item) in parsed.iter().enumerate() {
+                match *item {
+                    Some(ref item) if item.value() == value => match best {
+                        Some((_, quality)) if quality >= item.quality() => (),
+                        _ => best = Some((idx, item.quality())),
+                    },
+                    _ => (),
+                }
+            }
+            best.map(|(idx, _)| idx)
+        };
+
+        let mut seen: Vec<&T> = Vec::new();
+        let mut items = Vec::new();
+        for (raw, item) in raws.iter().zip(&parsed) {
+            match *item {
+                None => items.push((*raw).to_owned()),
+                Some(ref item) if !seen.contains(&item.value()) => {
+                    seen.push(item.value());
+                    let idx = best(item.value()).expect("value is in the list");
+                    items.push(raws[idx].to_owned());
+                }
+                Some(_) => (),
+            }
+        }
+        self.set_raw_items(items);
+    }
+}
+
+fn mentions<T: FromStr + PartialEq>(raw: &str, value: &T) -> bool {
+    raw.parse::<QualityValue<T>>()
+        .map(|item| item.value() == value)
+        .unwrap_or(false)
+}
+
+impl<T> From<FlatCsv> for QualityList<T> {
+    fn from(flat: FlatCsv) -> Self {
+        QualityList {
+            flat,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> From<HeaderValue> for QualityList<T> {
+    fn from(value: HeaderValue) -> Self {
+        FlatCsv::from(value).into()
+    }
+}
+
+impl<'a, T> From<&'a QualityList<T>> for HeaderValue {
+    fn from(list: &'a QualityList<T>) -> HeaderValue {
+        (&list.flat).into()
+    }
+}
+
+impl<T> TryFromValues for QualityList<T> {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        Ok(values.collect::<FlatCsv>().into())
+    }
+}
+
+/// # Panics
+///
+/// Panics if an item isn't a valid `HeaderValue`, so invalid items have to
+/// be filtered out first.
+impl<T: fmt::Display> FromIterator<QualityValue<T>> for QualityList<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = QualityValue<T>>,
+    {
+        iter.into_iter()
+            .map(|item| {
+                item.to_string()
+                    .parse::<HeaderValue>()
+                    .expect("quality items are valid HeaderValues")
+            })
+            .collect::<FlatCsv>()
+            .into()
+    }
+}
+
+impl<T> fmt::Debug for QualityList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.flat, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::q;
+
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    struct Token(String);
+
+    impl FromStr for Token {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Token, ()> {
+            if !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'*') {
+                Ok(Token(s.to_owned()))
+            } else {
+                Err(())
+            }
+        }
+    }
+
+    impl fmt::Display for Token {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl Specificity for Token {}
+
+    impl Wildcard for Token {
+        fn is_wildcard(&self) -> bool {
+            self.0 == "*"
+        }
+    }
+
+    fn t(s: &str) -> Token {
+        Token(s.to_owned())
+    }
+
+    fn list(s: &'static str) -> QualityList<Token> {
+        HeaderValue::from_static(s).into()
+    }
+
+    fn raw(list: &QualityList<Token>) -> &str {
+        list.as_raw_str().unwrap()
+    }
+
+    #[test]
+    fn to_vec_sorted() {
+        let items = list("a;q=0.5, b, (invalid), , c;q=0.5").to_vec();
+        assert_eq!(
+            items,
+            vec![
+                QualityValue::new(t("b"), q(1000)),
+                QualityValue::new(t("a"), q(500)),
+                QualityValue::new(t("c"), q(500)),
+            ]
+        );
+    }
+
+    #[test]
+    fn is_empty() {
+        assert!(list("").is_empty());
+        assert!(list(" , ").is_empty());
+        assert!(!list("a").is_empty());
+    }
+
+    #[test]
+    fn wildcard_and_negotiate() {
+        let list = list("a;q=0, *;q=0.3, b");
+        assert_eq!(list.quality_of(&t("a")), Some(q(0)));
+        assert_eq!(list.quality_of(&t("c")), Some(q(300)));
+        assert_eq!(list.negotiate(&[t("a"), t("c")], &[]), Some(t("c")));
+        assert_eq!(list.negotiate(&[t("c"), t("b")], &[]), Some(t("b")));
+    }
+
+    #[test]
+    fn quality_map_highest_wins() {
+        let map = list("a;q=0.2, b, a;q=0.7").quality_map();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&t("a")], q(700));
+        assert_eq!(map[&t("b")], q(1000));
+    }
+
+    #[test]
+    fn insert_remove_dedup() {
+        let mut list = list("a, (x), b;q=0.5, a;q=0.1");
+        list.insert(t("a"), q(200));
+        assert_eq!(raw(&list), "a; q=0.2, (x), b; q=0.5");
+
+        list.insert(t("c"), q(1000));
+        assert_eq!(raw(&list), "a; q=0.2, (x), b; q=0.5, c");
+
+        assert!(list.remove(&t("b")));
+        assert!(!list.remove(&t("b")));
+        assert_eq!(raw(&list), "a; q=0.2, (x), c");
+
+        let mut list = self::list("a;q=0.1, b, a;q=0.9");
+        list.dedup();
+        assert_eq!(raw(&list), "a; q=0.9, b");
+    }
+
+    #[test]
+    fn rewrite_keeps_params_and_invalid_weights() {
+        let mut list = list("a;x=\"1;2\";q=0.5, b;q=2, c");
+        assert!(list.remove(&t("c")));
+        assert_eq!(raw(&list), "a;x=\"1;2\"; q=0.5, b;q=2");
+    }
+
+    #[test]
+    fn insert_invalid_is_ignored() {
+        let mut list = list("a");
+        list.insert(Token("b\u{7f}".into()), q(500));
+        assert_eq!(raw(&list), "a");
+    }
+
+    #[test]
+    fn append_raw() {
+        let mut list = list("");
+        list.append_raw(&HeaderValue::from_static("a"));
+        list.append_raw(&HeaderValue::from_static("b;q=0.5"));
+        assert_eq!(raw(&list), "a, b;q=0.5");
+    }
+
+    #[test]
+    fn from_iter() {
+        let list = vec![
+            QualityValue::from(t("a")),
+            QualityValue::new(t("b"), q(500)),
+        ]
+        .into_iter()
+        .collect::<QualityList<Token>>();
+        assert_eq!(raw(&list), "a, b; q=0.5");
+    }
+}
@@ -0,0 +1,716 @@
+use std::cmp;
+use std::convert::TryFrom;
+use std::default::Default;
+use std::fmt;
+use std::str;
+
+#[cfg(test)]
+use self::internal::IntoQuality;
+
+/// Represents a quality used in quality values.
+///
+/// Can be created from an `f32` with `Quality::try_from`.
+///
+/// # Implementation notes
+///
+/// The quality value is defined as a number between 0 and 1 with three decimal places. This means
+/// there are 1001 possible values. Since floating point numbers are not exact and the smallest
+/// floating point data type (`f32`) consumes four bytes, this crate uses an `u16` value to store
+/// the quality internally. For example, `Quality(532)` matches the quality `q=0.532`.
+///
+/// [RFC7231 Section 5.3.1](https://tools.ietf.org/html/rfc7231#section-5.3.1)
+/// gives more information on quality values in HTTP header fields.
+#[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd, Hash)]
+pub struct Quality(u16);
+
+impl Default for Quality {
+    fn default() -> Quality {
+        Quality(1000)
+    }
+}
+
+impl Quality {
+    pub(crate) const ZERO: Quality = Quality(0);
+    /// The lowest non-zero quality, `0.001`.
+    pub(crate) const LOWEST: Quality = Quality(1);
+
+    /// Create a quality from thousandths, so `600` is `0.6`.
+    ///
+    /// Returns `None` if `millis` is larger than `1000`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::Quality;
+    ///
+    /// assert_eq!(Quality::from_millis(600).unwrap().to_string(), "0.6");
+    /// assert!(Quality::from_millis(1001).is_none());
+    /// ```
+    pub const fn from_millis(millis: u16) -> Option<Quality> {
+        if millis <= 1000 {
+            Some(Quality(millis))
+        } else {
+            None
+        }
+    }
+
+    /// The quality in thousandths, from `0` to `1000`.
+    ///
+    /// Unlike a float, this compares and stores without rounding.
+    pub const fn as_millis(&self) -> u16 {
+        self.0
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+/// Formats the quality the same way it is serialized in a header, with
+/// trailing zeros stripped: `1`, `0.5`, `0.333` or `0`.
+impl fmt::Display for Quality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            1000 => f.write_str("1"),
+            0 => f.write_str("0"),
+            x => write!(f, "0.{}", format!("{:03}", x).trim_end_matches('0')),
+        }
+    }
+}
+
+error_type!(InvalidQuality);
+
+impl TryFrom<f32> for Quality {
+    type Error = InvalidQuality;
+
+    fn try_from(f: f32) -> Result<Quality, InvalidQuality> {
+        if (0f32..=1f32).contains(&f) {
+            Ok(from_f32(f))
+        } else {
+            Err(InvalidQuality { _inner: () })
+        }
+    }
+}
+
+/// Represents an item with a quality value as defined in
+/// [RFC7231](https://tools.ietf.org/html/rfc7231#section-5.3.1).
+#[derive(Clone, PartialEq, Debug)]
+pub struct QualityValue<T> {
+    /// The actual contents of the field.
+    value: T,
+    /// The quality (client or server preference) for the value.
+    quality: Quality,
+}
+
+impl<T> QualityValue<T> {
+    /// Creates a new `QualityValue` from an item and a quality.
+    pub const fn new(value: T, quality: Quality) -> QualityValue<T> {
+        QualityValue { value, quality }
+    }
+
+    /// Creates a new `QualityValue` with a quality of 1, the same as
+    /// `QualityValue::from(value)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{Encoding, QualityValue};
+    ///
+    /// assert_eq!(
+    ///     QualityValue::q1(Encoding::Gzip),
+    ///     QualityValue::from(Encoding::Gzip),
+    /// );
+    /// ```
+    pub const fn q1(value: T) -> QualityValue<T> {
+        QualityValue {
+            value,
+            quality: Quality(1000),
+        }
+    }
+
+    /// Get a reference to the value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Get the quality of the value.
+    pub fn quality(&self) -> Quality {
+        self.quality
+    }
+
+    /// Consume this `QualityValue`, returning the inner value.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Serialize with the quality padded to three decimals, as in
+    /// `gzip; q=0.500`, for peers that don't accept the shorter form.
+    ///
+    /// The `Display` implementation, which headers are encoded with, strips
+    /// trailing zeros instead (`gzip; q=0.5`). Both omit a quality of 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use headers::{Encoding, Quality, QualityValue};
+    ///
+    /// let gzip = QualityValue::new(Encoding::Gzip, Quality::try_from(0.5).unwrap());
+    /// assert_eq!(gzip.to_string(), "gzip; q=0.5");
+    /// assert_eq!(gzip.serialize_padded(), "gzip; q=0.500");
+    /// ```
+    pub fn serialize_padded(&self) -> String
+    where
+        T: fmt::Display,
+    {
+        match self.quality.0 {
+            1000 => self.value.to_string(),
+            x => format!("{}; q=0.{:03}", self.value, x),
+        }
+    }
+}
+
+impl<T> From<T> for QualityValue<T> {
+    fn from(value: T) -> QualityValue<T> {
+        QualityValue {
+            value,
+            quality: Quality::default(),
+        }
+    }
+}
+
+impl<T: Eq> Eq for QualityValue<T> {}
+
+/// Orders by quality first, with the values breaking ties, so that only
+/// equal `QualityValue`s compare as equal.
+impl<T: PartialOrd> cmp::PartialOrd for QualityValue<T> {
+    fn partial_cmp(&self, other: &QualityValue<T>) -> Option<cmp::Ordering> {
+        match self.quality.cmp(&other.quality) {
+            cmp::Ordering::Equal => self.value.partial_cmp(&other.value),
+            ord => Some(ord),
+        }
+    }
+}
+
+/// A total order, by quality first and value second.
+///
+/// Since a `Quality` is an integer number of thousandths, there is no `NaN`
+/// to break the order. An ascending sort puts the lowest quality first; to
+/// sort by preference, reverse it.
+///
+/// # Example
+///
+/// ```
+/// use std::cmp::Reverse;
+/// use headers::{Encoding, QualityValue};
+///
+/// let mut items = vec![
+///     "gzip;q=0.5".parse::<QualityValue<Encoding>>().unwrap(),
+///     "br".parse().unwrap(),
+/// ];
+/// items.sort_by_key(|item| Reverse(item.clone()));
+/// assert_eq!(items[0].value(), &Encoding::Brotli);
+/// ```
+impl<T: Ord> cmp::Ord for QualityValue<T> {
+    fn cmp(&self, other: &QualityValue<T>) -> cmp::Ordering {
+        self.quality
+            .cmp(&other.quality)
+            .then_with(|| self.value.cmp(&other.value))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for QualityValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)?;
+        match self.quality.0 {
+            1000 => Ok(()),
+            _ => write!(f, "; q={}", self.quality),
+        }
+    }
+}
+
+/// An error parsing a [`QualityValue`].
+///
+/// Tells apart whether the value itself, or its `q` parameter, was invalid.
+#[derive(Debug)]
+pub struct InvalidQualityValue {
+    kind: InvalidKind,
+}
+
+#[derive(Debug)]
+enum InvalidKind {
+    Value,
+    Quality,
+}
+
+impl InvalidQualityValue {
+    fn value() -> InvalidQualityValue {
+        InvalidQualityValue {
+            kind: InvalidKind::Value,
+        }
+    }
+
+    fn quality() -> InvalidQualityValue {
+        InvalidQualityValue {
+            kind: InvalidKind::Quality,
+        }
+    }
+
+    /// Whether the value part could not be parsed.
+    pub fn is_value(&self) -> bool {
+        matches!(self.kind, InvalidKind::Value)
+    }
+
+    /// Whether the `q` parameter could not be parsed.
+    pub fn is_quality(&self) -> bool {
+        matches!(self.kind, InvalidKind::Quality)
+    }
+}
+
+impl fmt::Display for InvalidQualityValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            InvalidKind::Value => f.write_str("invalid value in quality item"),
+            InvalidKind::Quality => f.write_str("invalid quality in quality item"),
+        }
+    }
+}
+
+impl std::error::Error for InvalidQualityValue {}
+
+impl From<InvalidQualityValue> for crate::Error {
+    fn from(_: InvalidQualityValue) -> crate::Error {
+        crate::Error::invalid()
+    }
+}
+
+impl<T: str::FromStr> str::FromStr for QualityValue<T> {
+    type Err = InvalidQualityValue;
+
+    fn from_str(s: &str) -> Result<QualityValue<T>, InvalidQualityValue> {
+        // Set defaults used if parsing fails.
+        let mut raw_item = s;
+        let mut quality = Quality::default();
+
+        let parts: Vec<&str> = match split_weight(s) {
+            Some((item, weight)) => vec![weight.trim(), item.trim()],
+            None => vec![s.trim()],
+        };
+        if parts.len() == 2 {
+            if parts[0].len() < 2 {
+                return Err(InvalidQualityValue::quality());
+            }
+            // `weight = OWS ";" OWS "q=" qvalue`, but clients also send
+            // whitespace around the `=`.
+            if let Some((name, value)) = parts[0].split_once('=') {
+                if name.trim_end().eq_ignore_ascii_case("q") {
+                    quality = parse_quality(value.trim_start())
+                        .ok_or_else(InvalidQualityValue::quality)?;
+                    raw_item = parts[1];
+                }
+            }
+        }
+
+        raw_item
+            .parse::<T>()
+            .map(|item| QualityValue::new(item, quality))
+            .map_err(|_| InvalidQualityValue::value())
+    }
+}
+
+/// Split `s` at its last `;` that isn't inside a quoted parameter value,
+/// such as in `aes128gcm; keyid="a;b"`.
+fn split_weight(s: &str) -> Option<(&str, &str)> {
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut last = None;
+    for (idx, b) in s.bytes().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match b {
+            b'\\' if in_quotes => escaped = true,
+            b'"' => in_quotes = !in_quotes,
+            b';' if !in_quotes => last = Some(idx),
+            _ => (),
+        }
+    }
+    last.map(|idx| (&s[..idx], &s[idx + 1..]))
+}
+
+/// Parse a `qvalue`, which has at most three decimal places.
+///
+/// ```text
+/// qvalue = ( "0" [ "." 0*3DIGIT ] )
+///        / ( "1" [ "." 0*3("0") ] )
+/// ```
+fn parse_quality(s: &str) -> Option<Quality> {
+    let (int, frac) = match s.find('.') {
+        Some(idx) => (&s[..idx], &s[idx + 1..]),
+        None => (s, ""),
+    };
+
+    if frac.len() > 3 || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let millis = frac
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(3)
+        .fold(0u16, |acc, b| acc * 10 + u16::from(b - b'0'));
+
+    match int {
+        "0" => Some(Quality(millis)),
+        "1" if millis == 0 => Some(Quality(1000)),
+        _ => None,
+    }
+}
+
+#[inline]
+fn from_f32(f: f32) -> Quality {
+    // this function is only used internally. A check that `f` is within range
+    // should be done before calling this method. Just in case, this
+    // debug_assert should catch if we were forgetful
+    debug_assert!(
+        (0f32..=1f32).contains(&f),
+        "q value must be between 0.0 and 1.0"
+    );
+    Quality((f * 1000f32).round() as u16)
+}
+
+#[cfg(test)]
+pub(crate) fn q<T: IntoQuality>(val: T) -> Quality {
+    val.into_quality()
+}
+
+#[cfg(test)]
+impl proptest::arbitrary::Arbitrary for Quality {
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<std::ops::RangeInclusive<u16>, fn(u16) -> Quality>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+
+        (0..=1000u16).prop_map(Quality)
+    }
+}
+
+#[cfg(test)]
+mod internal {
+    use super::Quality;
+
+    // Keep the panicking `q` function around for tests, generic over
+    // `f32` and `u16`. The public, non-panicking conversion is `TryFrom`.
+
+    pub trait IntoQuality: Sealed + Sized {
+        fn into_quality(self) -> Quality;
+    }
+
+    impl IntoQuality for f32 {
+        fn into_quality(self) -> Quality {
+            assert!(
+                (0f32..=1f32).contains(&self),
+                "float must be between 0.0 and 1.0"
+            );
+            super::from_f32(self)
+        }
+    }
+
+    impl IntoQuality for u16 {
+        fn into_quality(self) -> Quality {
+            assert!(self <= 1000, "u16 must be between 0 and 1000");
+            Quality(self)
+        }
+    }
+
+    pub trait Sealed {}
+    impl Sealed for u16 {}
+    impl Sealed for f32 {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quality_millis() {
+        assert_eq!(q(600).as_millis(), 600);
+        assert_eq!(Quality::default().as_millis(), 1000);
+        assert_eq!(Quality::from_millis(0), Some(Quality::ZERO));
+        assert_eq!(Quality::from_millis(1000), Some(Quality::default()));
+        assert_eq!(Quality::from_millis(1001), None);
+        assert_eq!(Quality::from_millis(u16::MAX), None);
+    }
+
+    #[test]
+    fn test_quality_item_fmt_q_1() {
+        let x = QualityValue::from("foo");
+        assert_eq!(format!("{}", x), "foo");
+    }
+    #[test]
+    fn test_quality_item_q1() {
+        const GZIP: QualityValue<&str> = QualityValue::q1("gzip");
+
+        assert_eq!(GZIP, QualityValue::from("gzip"));
+        assert_eq!(GZIP.quality(), Quality::default());
+        assert_eq!(QualityValue::q1(42u8), QualityValue::from(42u8));
+        assert_eq!(QualityValue::from(vec![1, 2]).quality(), Quality(1000));
+    }
+
+    #[test]
+    fn test_quality_item_fmt_q_0001() {
+        let x = QualityValue::new("foo", Quality(1));
+        assert_eq!(format!("{}", x), "foo; q=0.001");
+    }
+    #[test]
+    fn test_quality_item_fmt_q_05() {
+        let x = QualityValue::new("foo", Quality(500));
+        assert_eq!(format!("{}", x), "foo; q=0.5");
+    }
+
+    #[test]
+    fn test_quality_item_fmt_encoding() {
+        let x = QualityValue::new(crate::Encoding::Gzip, Quality(500));
+        assert_eq!(x.to_string(), "gzip; q=0.5");
+
+        let x = QualityValue::new(mime::TEXT_HTML, Quality(1000));
+        assert_eq!(x.to_string(), "text/html");
+    }
+
+    #[test]
+    fn test_quality_item_fmt_q_0() {
+        let x = QualityValue::new("foo", Quality(0));
+        assert_eq!(x.to_string(), "foo; q=0");
+    }
+
+    #[test]
+    fn test_quality_item_serialize_padded() {
+        assert_eq!(
+            QualityValue::new("foo", Quality(500)).serialize_padded(),
+            "foo; q=0.500"
+        );
+        assert_eq!(
+            QualityValue::new("foo", Quality(1)).serialize_padded(),
+            "foo; q=0.001"
+        );
+        assert_eq!(
+            QualityValue::new("foo", Quality(0)).serialize_padded(),
+            "foo; q=0.000"
+        );
+        assert_eq!(QualityValue::from("foo").serialize_padded(), "foo");
+    }
+
+    #[test]
+    fn test_quality_item_compact_roundtrip() {
+        for s in &["foo; q=0.500", "foo;q=0.50", "foo; q=0.5"] {
+            let x = s.parse::<QualityValue<String>>().unwrap();
+            assert_eq!(x.to_string(), "foo; q=0.5");
+        }
+    }
+
+    #[test]
+    fn test_quality_item_from_str_params() {
+        let x: QualityValue<String> = "aes128gcm; keyid=\"a;q=0\"; q=0.5".parse().unwrap();
+        assert_eq!(x.value(), "aes128gcm; keyid=\"a;q=0\"");
+        assert_eq!(x.quality(), Quality(500));
+
+        let x: QualityValue<String> = "aes128gcm; keyid=\"a;q=0\"".parse().unwrap();
+        assert_eq!(x.value(), "aes128gcm; keyid=\"a;q=0\"");
+        assert_eq!(x.quality(), Quality(1000));
+    }
+
+    #[test]
+    fn test_quality_item_ord() {
+        let mut items = vec![
+            QualityValue::new("b", Quality(500)),
+            QualityValue::new("a", Quality(1000)),
+            QualityValue::new("c", Quality(0)),
+            QualityValue::new("a", Quality(500)),
+        ];
+        items.sort();
+
+        assert_eq!(
+            items,
+            vec![
+                QualityValue::new("c", Quality(0)),
+                QualityValue::new("a", Quality(500)),
+                QualityValue::new("b", Quality(500)),
+                QualityValue::new("a", Quality(1000)),
+            ]
+        );
+        assert!(QualityValue::new("a", Quality(1)) > QualityValue::new("b", Quality(0)));
+        assert_eq!(
+            QualityValue::new("a", Quality(500)).cmp(&QualityValue::new("a", Quality(500))),
+            cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_quality_fmt() {
+        assert_eq!(Quality(1000).to_string(), "1");
+        assert_eq!(Quality(500).to_string(), "0.5");
+        assert_eq!(Quality(333).to_string(), "0.333");
+        assert_eq!(Quality(0).to_string(), "0");
+    }
+
+    #[test]
+    fn test_quality_item_from_str1() {
+        let x: QualityValue<String> = "chunked".parse().unwrap();
+        assert_eq!(
+            x,
+            QualityValue {
+                value: "chunked".to_owned(),
+                quality: Quality(1000),
+            }
+        );
+    }
+    #[test]
+    fn test_quality_item_from_str2() {
+        let x: QualityValue<String> = "chunked; q=1".parse().unwrap();
+        assert_eq!(
+            x,
+            QualityValue {
+                value: "chunked".to_owned(),
+                quality: Quality(1000),
+            }
+        );
+    }
+    #[test]
+    fn test_quality_item_from_str3() {
+        let x: QualityValue<String> = "gzip; q=0.5".parse().unwrap();
+        assert_eq!(
+            x,
+            QualityValue {
+                value: "gzip".to_owned(),
+                quality: Quality(500),
+            }
+        );
+    }
+    #[test]
+    fn test_quality_item_from_str4() {
+        let x: QualityValue<String> = "gzip; q=0.273".parse().unwrap();
+        assert_eq!(
+            x,
+            QualityValue {
+                value: "gzip".to_owned(),
+                quality: Quality(273),
+            }
+        );
+    }
+    #[test]
+    fn test_quality_item_from_str5() {
+        assert!("gzip; q=0.2739999".parse::<QualityValue<String>>().is_err());
+    }
+
+    #[test]
+    fn test_quality_item_from_str6() {
+        assert!("gzip; q=2".parse::<QualityValue<String>>().is_err());
+    }
+
+    #[test]
+    fn test_quality_item_from_str7() {
+        assert!("gzip; q=1.001".parse::<QualityValue<String>>().is_err());
+        assert!("gzip; q=0.5x".parse::<QualityValue<String>>().is_err());
+        assert!("gzip; q=".parse::<QualityValue<String>>().is_err());
+    }
+
+    #[test]
+    fn test_quality_item_from_str_uppercase_q() {
+        let x: QualityValue<String> = "gzip;Q=0.5".parse().unwrap();
+        assert_eq!(x, QualityValue::new("gzip".to_owned(), Quality(500)));
+    }
+
+    #[test]
+    fn test_quality_item_from_str_whitespace() {
+        for s in &[
+            "gzip;q=0.5",
+            "gzip; q=0.5",
+            "gzip ;q=0.5",
+            "gzip ; q = 0.5",
+            "gzip;q =0.5",
+            "gzip;\tq=\t0.5 ",
+        ] {
+            let x: QualityValue<String> = s.parse().unwrap();
+            assert_eq!(
+                x,
+                QualityValue::new("gzip".to_owned(), Quality(500)),
+                "{:?}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_quality_item_from_str_whitespace_malformed() {
+        assert!("gzip; q = ".parse::<QualityValue<String>>().is_err());
+        assert!("gzip; q = 0. 5".parse::<QualityValue<String>>().is_err());
+        assert!("gzip; q = 0.5 0".parse::<QualityValue<String>>().is_err());
+    }
+
+    #[derive(Debug)]
+    struct Digit(u8);
+
+    impl str::FromStr for Digit {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Digit, ()> {
+            match s.as_bytes() {
+                [b @ b'0'..=b'9'] => Ok(Digit(b - b'0')),
+                _ => Err(()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_quality_item_from_str_error_kind() {
+        let x = "7; q=0.5".parse::<QualityValue<Digit>>().unwrap();
+        assert_eq!(x.value().0, 7);
+
+        let err = "x; q=0.5".parse::<QualityValue<Digit>>().unwrap_err();
+        assert!(err.is_value());
+        assert!(!err.is_quality());
+        assert_eq!(err.to_string(), "invalid value in quality item");
+
+        let err = "7; q=5".parse::<QualityValue<Digit>>().unwrap_err();
+        assert!(err.is_quality());
+        assert!(!err.is_value());
+        assert_eq!(err.to_string(), "invalid quality in quality item");
+
+        // The quality is checked first, so both being invalid reports that.
+        let err = "x; q=5".parse::<QualityValue<Digit>>().unwrap_err();
+        assert!(err.is_quality());
+    }
+
+    #[test]
+    fn test_quality_item_ordering() {
+        let x: QualityValue<String> = "gzip; q=0.5".parse().unwrap();
+        let y: QualityValue<String> = "gzip; q=0.273".parse().unwrap();
+        assert!(x > y)
+    }
+
+    #[test]
+    fn test_quality() {
+        assert_eq!(q(0.5), Quality(500));
+        assert_eq!(Quality::try_from(0.333).unwrap(), Quality(333));
+    }
+
+    #[test]
+    fn test_quality_invalid() {
+        assert!(Quality::try_from(-1.0).is_err());
+        assert!(Quality::try_from(2.0).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_quality_invalid_panics() {
+        q(2.0);
+    }
+
+    #[test]
+    fn test_fuzzing_bugs() {
+        assert!("99999;".parse::<QualityValue<String>>().is_err());
+        assert!("\x0d;;;=\u{d6aa}==".parse::<QualityValue<String>>().is_ok())
+    }
+}
@@ -0,0 +1,525 @@
+//! Structured Field Values, defined in
+//! [RFC8941](https://www.rfc-editor.org/rfc/rfc8941).
+//!
+//! Headers like `Content-Digest`, `Priority` or `Sec-Fetch-*` are defined
+//! as a structured item, list or dictionary. These parsers follow the
+//! algorithms of [section 4.2](https://www.rfc-editor.org/rfc/rfc8941#section-4.2),
+//! and return `None` for anything the RFC says to fail on.
+
+#![cfg_attr(not(test), allow(dead_code))]
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// A bare item, which is an item without parameters.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum BareItem {
+    Integer(i64),
+    Decimal(f64),
+    String(String),
+    Token(String),
+    ByteSequence(Vec<u8>),
+    Boolean(bool),
+}
+
+#[cfg(test)]
+impl BareItem {
+    pub(crate) fn as_integer(&self) -> Option<i64> {
+        match *self {
+            BareItem::Integer(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_bool(&self) -> Option<bool> {
+        match *self {
+            BareItem::Boolean(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_bytes(&self) -> Option<&[u8]> {
+        match *self {
+            BareItem::ByteSequence(ref b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) type Parameters = Vec<(String, BareItem)>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Item {
+    pub(crate) bare: BareItem,
+    pub(crate) params: Parameters,
+}
+
+/// A member of a list or dictionary.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Member {
+    Item(Item),
+    InnerList(Vec<Item>, Parameters),
+}
+
+#[cfg(test)]
+impl Member {
+    pub(crate) fn as_item(&self) -> Option<&Item> {
+        match *self {
+            Member::Item(ref item) => Some(item),
+            Member::InnerList(..) => None,
+        }
+    }
+}
+
+/// Parse an `sf-item`.
+pub(crate) fn parse_item(s: &str) -> Option<Item> {
+    Parser::new(s).top_level(Parser::item)
+}
+
+/// Parse an `sf-list`.
+pub(crate) fn parse_list(s: &str) -> Option<Vec<Member>> {
+    Parser::new(s).top_level(Parser::list)
+}
+
+/// Parse an `sf-dictionary`.
+///
+/// If a key is repeated, the last value wins, but keeps the position of
+/// the first.
+pub(crate) fn parse_dictionary(s: &str) -> Option<Vec<(String, Member)>> {
+    Parser::new(s).top_level(Parser::dictionary)
+}
+
+/// Check if `s` is a valid `key`, as used in dictionaries and parameters.
+pub(crate) fn is_key(s: &str) -> bool {
+    let mut parser = Parser::new(s);
+    parser.key().is_some() && parser.is_eof()
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Parser<'a> {
+        Parser {
+            input: s.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn top_level<T>(mut self, parse: fn(&mut Parser<'a>) -> Option<T>) -> Option<T> {
+        self.skip_sp();
+        let value = parse(&mut self)?;
+        self.skip_sp();
+        if self.is_eof() {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_eof(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    fn skip_sp(&mut self) {
+        while self.eat(b' ') {}
+    }
+
+    fn skip_ows(&mut self) {
+        while self.eat(b' ') || self.eat(b'\t') {}
+    }
+
+    fn list(&mut self) -> Option<Vec<Member>> {
+        let mut members = Vec::new();
+        while !self.is_eof() {
+            members.push(self.member()?);
+            if !self.members_continue()? {
+                break;
+            }
+        }
+        Some(members)
+    }
+
+    fn dictionary(&mut self) -> Option<Vec<(String, Member)>> {
+        let mut members: Vec<(String, Member)> = Vec::new();
+        while !self.is_eof() {
+            let key = self.key()?;
+            let member = if self.eat(b'=') {
+                self.member()?
+            } else {
+                Member::Item(Item {
+                    bare: BareItem::Boolean(true),
+                    params: self.parameters()?,
+                })
+            };
+
+            match members.iter_mut().find(|(k, _)| *k == key) {
+                Some(existing) => existing.1 = member,
+                None => members.push((key, member)),
+            }
+
+            if !self.members_continue()? {
+                break;
+            }
+        }
+        Some(members)
+    }
+
+    // After a list or dictionary member: `OWS "," OWS`, or the end.
+    fn members_continue(&mut self) -> Option<bool> {
+        self.skip_ows();
+        if self.is_eof() {
+            return Some(false);
+        }
+        if !self.eat(b',') {
+            return None;
+        }
+        self.skip_ows();
+        if self.is_eof() {
+            // trailing comma
+            return None;
+        }
+        Some(true)
+    }
+
+    fn member(&mut self) -> Option<Member> {
+        if self.peek() == Some(b'(') {
+            self.inner_list()
+        } else {
+            self.item().map(Member::Item)
+        }
+    }
+
+    fn inner_list(&mut self) -> Option<Member> {
+        if !self.eat(b'(') {
+            return None;
+        }
+        let mut items = Vec::new();
+        loop {
+            self.skip_sp();
+            if self.eat(b')') {
+                return Some(Member::InnerList(items, self.parameters()?));
+            }
+            items.push(self.item()?);
+            match self.peek() {
+                Some(b' ') | Some(b')') => (),
+                _ => return None,
+            }
+        }
+    }
+
+    fn item(&mut self) -> Option<Item> {
+        let bare = self.bare_item()?;
+        let params = self.parameters()?;
+        Some(Item { bare, params })
+    }
+
+    fn bare_item(&mut self) -> Option<BareItem> {
+        match self.peek()? {
+            b'-' | b'0'..=b'9' => self.number(),
+            b'"' => self.string(),
+            b'*' | b'a'..=b'z' | b'A'..=b'Z' => self.token(),
+            b':' => self.byte_sequence(),
+            b'?' => self.boolean(),
+            _ => None,
+        }
+    }
+
+    fn parameters(&mut self) -> Option<Parameters> {
+        let mut params: Parameters = Vec::new();
+        while self.eat(b';') {
+            self.skip_sp();
+            let key = self.key()?;
+            let value = if self.eat(b'=') {
+                self.bare_item()?
+            } else {
+                BareItem::Boolean(true)
+            };
+
+            match params.iter_mut().find(|(k, _)| *k == key) {
+                Some(existing) => existing.1 = value,
+                None => params.push((key, value)),
+            }
+        }
+        Some(params)
+    }
+
+    fn key(&mut self) -> Option<String> {
+        let start = self.pos;
+        match self.bump()? {
+            b'a'..=b'z' | b'*' => (),
+            _ => return None,
+        }
+        while let Some(b) = self.peek() {
+            match b {
+                b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b'*' => self.pos += 1,
+                _ => break,
+            }
+        }
+        Some(self.slice(start).to_owned())
+    }
+
+    fn number(&mut self) -> Option<BareItem> {
+        let start = self.pos;
+        let negative = self.eat(b'-');
+        let digits_start = self.pos;
+        let mut is_decimal = false;
+
+        while let Some(b) = self.peek() {
+            match b {
+                b'0'..=b'9' => self.pos += 1,
+                b'.' if !is_decimal => {
+                    let int_len = self.pos - digits_start;
+                    if int_len == 0 || int_len > 12 {
+                        return None;
+                    }
+                    is_decimal = true;
+                    self.pos += 1;
+                }
+                _ => break,
+            }
+            let len = self.pos - digits_start;
+            if (!is_decimal && len > 15) || (is_decimal && len > 16) {
+                return None;
+            }
+        }
+
+        let digits = self.slice(digits_start);
+        if digits.is_empty() {
+            return None;
+        }
+
+        if is_decimal {
+            let frac_len = digits.len() - digits.find('.')? - 1;
+            if frac_len == 0 || frac_len > 3 {
+                return None;
+            }
+            self.slice(start).parse().ok().map(BareItem::Decimal)
+        } else {
+            let n = digits.parse::<i64>().ok()?;
+            Some(BareItem::Integer(if negative { -n } else { n }))
+        }
+    }
+
+    fn string(&mut self) -> Option<BareItem> {
+        if !self.eat(b'"') {
+            return None;
+        }
+        let mut s = String::new();
+        loop {
+            match self.bump()? {
+                b'\\' => match self.bump()? {
+                    b @ b'"' | b @ b'\\' => s.push(char::from(b)),
+                    _ => return None,
+                },
+                b'"' => return Some(BareItem::String(s)),
+                b @ 0x20..=0x7e => s.push(char::from(b)),
+                _ => return None,
+            }
+        }
+    }
+
+    fn token(&mut self) -> Option<BareItem> {
+        let start = self.pos;
+        match self.bump()? {
+            b'*' | b'a'..=b'z' | b'A'..=b'Z' => (),
+            _ => return None,
+        }
+        while let Some(b) = self.peek() {
+            if is_tchar(b) || b == b':' || b == b'/' {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        Some(BareItem::Token(self.slice(start).to_owned()))
+    }
+
+    fn byte_sequence(&mut self) -> Option<BareItem> {
+        if !self.eat(b':') {
+            return None;
+        }
+        let start = self.pos;
+        while let Some(b) = self.peek() {
+            match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'+' | b'/' | b'=' => self.pos += 1,
+                _ => break,
+            }
+        }
+        let encoded = self.slice(start);
+        if !self.eat(b':') {
+            return None;
+        }
+        STANDARD.decode(encoded).ok().map(BareItem::ByteSequence)
+    }
+
+    fn boolean(&mut self) -> Option<BareItem> {
+        if !self.eat(b'?') {
+            return None;
+        }
+        match self.bump()? {
+            b'0' => Some(BareItem::Boolean(false)),
+            b'1' => Some(BareItem::Boolean(true)),
+            _ => None,
+        }
+    }
+
+    fn slice(&self, start: usize) -> &'a str {
+        // Only ASCII bytes are ever consumed, so this is a char boundary.
+        let input: &'a [u8] = self.input;
+        ::std::str::from_utf8(&input[start..self.pos]).expect("sf parser only consumes ASCII")
+    }
+}
+
+fn is_tchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bare(s: &str) -> Option<BareItem> {
+        parse_item(s).map(|item| item.bare)
+    }
+
+    #[test]
+    fn booleans() {
+        assert_eq!(bare("?1"), Some(BareItem::Boolean(true)));
+        assert_eq!(bare("?0"), Some(BareItem::Boolean(false)));
+        assert_eq!(bare("?2"), None);
+        assert_eq!(bare("?"), None);
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(bare("42"), Some(BareItem::Integer(42)));
+        assert_eq!(bare("-42"), Some(BareItem::Integer(-42)));
+        assert_eq!(bare("000000000000042"), Some(BareItem::Integer(42)));
+        assert_eq!(
+            bare("999999999999999"),
+            Some(BareItem::Integer(999_999_999_999_999))
+        );
+        assert_eq!(bare("1000000000000000"), None);
+        assert_eq!(bare("-"), None);
+        assert_eq!(bare("4 2"), None);
+    }
+
+    #[test]
+    fn decimals() {
+        assert_eq!(bare("1.5"), Some(BareItem::Decimal(1.5)));
+        assert_eq!(bare("-0.25"), Some(BareItem::Decimal(-0.25)));
+        assert_eq!(bare("1.2345"), None);
+        assert_eq!(bare("1."), None);
+    }
+
+    #[test]
+    fn strings_tokens_and_bytes() {
+        assert_eq!(
+            bare(r#""say \"hi\"""#),
+            Some(BareItem::String("say \"hi\"".into()))
+        );
+        assert_eq!(bare(r#""bad \n""#), None);
+        assert_eq!(bare("text/html"), Some(BareItem::Token("text/html".into())));
+        assert_eq!(bare(":AAEC:"), Some(BareItem::ByteSequence(vec![0, 1, 2])));
+        assert_eq!(bare(":AAEC"), None);
+    }
+
+    #[test]
+    fn item_parameters() {
+        let item = parse_item("5; i; a=?0").unwrap();
+        assert_eq!(item.bare.as_integer(), Some(5));
+        assert_eq!(
+            item.params,
+            vec![
+                ("i".to_owned(), BareItem::Boolean(true)),
+                ("a".to_owned(), BareItem::Boolean(false)),
+            ]
+        );
+    }
+
+    #[test]
+    fn dictionary() {
+        let dict = parse_dictionary("u=1, i, sha-256=:AAEC:;x=2").unwrap();
+
+        assert_eq!(dict.len(), 3);
+        assert_eq!(dict[0].0, "u");
+        assert_eq!(dict[0].1.as_item().unwrap().bare.as_integer(), Some(1));
+        assert_eq!(dict[1].0, "i");
+        assert_eq!(dict[1].1.as_item().unwrap().bare.as_bool(), Some(true));
+        let digest = dict[2].1.as_item().unwrap();
+        assert_eq!(digest.bare.as_bytes(), Some(&[0, 1, 2][..]));
+        assert_eq!(digest.params, vec![("x".to_owned(), BareItem::Integer(2))]);
+    }
+
+    #[test]
+    fn dictionary_duplicate_key() {
+        let dict = parse_dictionary("a=1, b=2, a=3").unwrap();
+        assert_eq!(dict.len(), 2);
+        assert_eq!(dict[0].0, "a");
+        assert_eq!(dict[0].1.as_item().unwrap().bare.as_integer(), Some(3));
+    }
+
+    #[test]
+    fn dictionary_invalid() {
+        assert_eq!(parse_dictionary("A=1"), None);
+        assert_eq!(parse_dictionary("a=1,"), None);
+        assert_eq!(parse_dictionary("a=1 b=2"), None);
+    }
+
+    #[test]
+    fn list() {
+        let list = parse_list("sugar, tea, (rum \"hot\");q=1").unwrap();
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(
+            list[1].as_item().unwrap().bare,
+            BareItem::Token("tea".into())
+        );
+        match list[2] {
+            Member::InnerList(ref items, ref params) => {
+                assert_eq!(items.len(), 2);
+                assert_eq!(items[1].bare, BareItem::String("hot".into()));
+                assert_eq!(params, &vec![("q".to_owned(), BareItem::Integer(1))]);
+            }
+            ref other => panic!("expected inner list, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn keys() {
+        assert!(is_key("sha-256"));
+        assert!(is_key("*a_b.c"));
+        assert!(!is_key("SHA-256"));
+        assert!(!is_key("1a"));
+        assert!(!is_key("a b"));
+        assert!(!is_key(""));
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(parse_list(""), Some(Vec::new()));
+        assert_eq!(parse_dictionary("  "), Some(Vec::new()));
+        assert_eq!(parse_item(""), None);
+    }
+}
@@ -35,6 +35,17 @@ impl HeaderValueString {
             .map(|value| HeaderValueString { value })
     }
 
+    /// Parse `src` with surrounding whitespace stripped, rejecting an empty
+    /// value or one that isn't visible ASCII.
+    pub(crate) fn trimmed(src: &str) -> Option<Self> {
+        let src = trim_ows(src);
+        if src.is_empty() {
+            return None;
+        }
+        let value = src.parse::<HeaderValue>().ok()?;
+        HeaderValueString::from_val(&value).ok()
+    }
+
     pub(crate) const fn from_static(src: &'static str) -> HeaderValueString {
         // A valid `str` (the argument)...
         HeaderValueString {
@@ -79,6 +90,11 @@ impl<'a> From<&'a HeaderValueString> for HeaderValue {
     }
 }
 
+/// Strip optional whitespace (`OWS`, spaces and tabs) around `src`.
+pub(crate) fn trim_ows(src: &str) -> &str {
+    src.trim_matches(|c| c == ' ' || c == '\t')
+}
+
 #[derive(Debug)]
 pub(crate) struct FromStrError(());
 
@@ -92,3 +108,26 @@ impl FromStr for HeaderValueString {
             .map_err(|_| FromStrError(()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trimmed_padded() {
+        let val = HeaderValueString::trimmed(" \thyper/0.14 ").unwrap();
+        assert_eq!(val.as_str(), "hyper/0.14");
+    }
+
+    #[test]
+    fn trimmed_rejects_empty() {
+        assert!(HeaderValueString::trimmed("").is_none());
+        assert!(HeaderValueString::trimmed(" \t ").is_none());
+    }
+
+    #[test]
+    fn trimmed_rejects_invalid() {
+        assert!(HeaderValueString::trimmed("caf\u{e9}").is_none());
+        assert!(HeaderValueString::trimmed("a\nb").is_none());
+    }
+}